@@ -1,7 +1,7 @@
 import * as THREE from 'three';
-import { createContext, GeometryClass, MaterialClass } from './index';
+import { createContext, ErrorCode, EventKind, GeometryClass, MaterialClass } from './index';
 
-import { test, describe, expect } from 'vitest';
+import { test, describe, expect, vi } from 'vitest';
 
 function mockTextureLoader(lib: any) {
     // mock texture loader so that it returns a dummy texture
@@ -66,6 +66,15 @@ describe('createObject', () => {
         expect(result).toBe(0);
     });
 
+    test('creates a TorusKnotGeometry + MeshStandardMaterial object', () => {
+        const lib = createContext();
+        const result = lib.createObject(
+            GeometryClass.TorusKnotGeometry,
+            MaterialClass.MeshStandardMaterial,
+        );
+        expect(result).toBe(0);
+    });
+
     test('Increases the object count', () => {
         const lib = createContext();
         const obj1 = lib.createObject(
@@ -81,6 +90,13 @@ describe('createObject', () => {
     })
 });
 
+/** Copies a string into WASM memory and returns its length in bytes, like the Rust side does */
+function writeString(memory: WebAssembly.Memory, ptr: number, text: string): number {
+    const bytes = new TextEncoder().encode(text);
+    new Uint8Array(memory.buffer, ptr, bytes.length).set(bytes);
+    return bytes.length;
+}
+
 const DEFAULT_TEXTURE = `http://localhost:8000/test_images/image.png`;
 const DEFAULT_TEXTURE_ID = 123;
 const addTextureFixture = async (rows: number | undefined = undefined, cols: number | undefined = undefined) => {
@@ -170,7 +186,7 @@ describe('setSpriteAnimationOffset', () => {
     test('Fails if texture has no rows/cols', async () => {
         const { lib, spriteObjectId } = await createSpriteFixture(undefined, undefined);
         const retcode = lib.setSpriteAnimationOffset(spriteObjectId, 0, 0);
-        expect(retcode).toBe(-1);
+        expect(retcode).toBe(ErrorCode.InvalidArgument);
     });
 
     test("Succeeds if texture has rows/cols", async () => {
@@ -230,3 +246,145 @@ describe('setPosition', () => {
         expect(object!.position.z).toBe(3);
     });
 });
+
+describe('error codes', () => {
+    test('Returns -1 for an unknown object id', () => {
+        const lib = createContext();
+        expect(lib.setPosition(42, 1, 2, 3)).toBe(ErrorCode.UnknownObject);
+        expect(ErrorCode.UnknownObject).toBe(-1);
+    });
+
+    test('Returns -2 for the wrong kind of object', () => {
+        const lib = createContext();
+        const objectId = lib.createObject(
+            GeometryClass.BoxGeometry,
+            MaterialClass.MeshBasicMaterial
+        );
+        expect(lib.setSpriteAnimationOffset(objectId, 0, 0)).toBe(ErrorCode.InvalidArgument);
+        expect(ErrorCode.InvalidArgument).toBe(-2);
+    });
+
+    test('Returns -4 when used before init', () => {
+        const lib = createContext();
+        expect(lib.clearScene(0)).toBe(ErrorCode.NotInitialized);
+        expect(lib.setCameraPosition(0, 0, 5)).toBe(ErrorCode.NotInitialized);
+        expect(ErrorCode.NotInitialized).toBe(-4);
+    });
+});
+
+const ATLAS_URL = 'http://localhost:8000/atlases/sheet.json';
+
+const loadAtlasFixture = async (frames: unknown) => {
+    const lib = createContext();
+    const memory = new WebAssembly.Memory({ initial: 1 });
+    lib.bindMemory(memory);
+
+    const loadedImages: string[] = [];
+    // @ts-ignore
+    lib.textureLoader.load = function (url: string, onLoad: (texture: THREE.Texture) => void) {
+        loadedImages.push(url);
+        const texture = new THREE.Texture();
+        onLoad(texture);
+        return texture;
+    }
+    vi.stubGlobal('fetch', vi.fn(async () => ({
+        json: async () => ({ frames, meta: { image: 'sheet.png', size: { w: 64, h: 32 } } }),
+    })));
+
+    const atlasId = lib.loadAtlas(0, writeString(memory, 0, ATLAS_URL));
+    await vi.waitFor(() => expect(lib.atlasIsReady(atlasId)).toBe(1));
+    vi.unstubAllGlobals();
+
+    const frame = (name: string) => {
+        const code = lib.getAtlasFrame(atlasId, 0, writeString(memory, 0, name), 256);
+        return code === 0 ? Array.from(new Float32Array(memory.buffer, 256, 4)) : code;
+    };
+    return { lib, loadedImages, frame };
+}
+
+describe('loadAtlas', () => {
+    test('Parses the hash format into UV rects', async () => {
+        const { loadedImages, frame } = await loadAtlasFixture({
+            walk_0: { frame: { x: 0, y: 0, w: 16, h: 16 } },
+            walk_1: { frame: { x: 16, y: 16, w: 32, h: 16 } },
+        });
+
+        expect(loadedImages).toEqual(['http://localhost:8000/atlases/sheet.png']);
+        // the JSON counts from the top-left corner, UVs from the bottom-left one
+        expect(frame('walk_0')).toEqual([0, 0.5, 0.25, 0.5]);
+        expect(frame('walk_1')).toEqual([0.25, 0, 0.5, 0.5]);
+    });
+
+    test('Parses the array format', async () => {
+        const { frame } = await loadAtlasFixture([
+            { filename: 'idle', frame: { x: 32, y: 0, w: 32, h: 32 } },
+        ]);
+        expect(frame('idle')).toEqual([0.5, 0, 0.5, 1]);
+    });
+
+    test('Skips rotated and unknown frames', async () => {
+        const { frame } = await loadAtlasFixture({
+            turned: { frame: { x: 0, y: 0, w: 16, h: 16 }, rotated: true },
+        });
+        expect(frame('turned')).toBe(ErrorCode.InvalidArgument);
+        expect(frame('missing')).toBe(ErrorCode.InvalidArgument);
+    });
+});
+
+describe('pollEvent', () => {
+    test('Returns -4 if WASM memory is not bound', () => {
+        const lib = createContext();
+        expect(lib.pollEvent(0)).toBe(ErrorCode.NotInitialized);
+    });
+
+    test('Dequeues events oldest first', () => {
+        const lib = createContext();
+        const memory = new WebAssembly.Memory({ initial: 1 });
+        lib.bindMemory(memory);
+        lib.initInputListeners();
+        window.dispatchEvent(new Event('blur'));
+        window.dispatchEvent(new Event('focus'));
+
+        const event = new Int32Array(memory.buffer, 0, 2);
+        expect(lib.pollEvent(0)).toBe(1);
+        expect(Array.from(event)).toEqual([EventKind.WindowBlur, 0]);
+        expect(lib.pollEvent(0)).toBe(1);
+        expect(Array.from(event)).toEqual([EventKind.WindowFocus, 0]);
+        expect(lib.pollEvent(0)).toBe(0);
+    });
+});
+
+describe('setObjectOpacity', () => {
+    const meshMaterial = (lib: ReturnType<typeof createContext>, id: number) =>
+        (lib.__OBJECTS.get(id) as THREE.Mesh).material as THREE.Material;
+
+    test('Copies a material shared with a clone', () => {
+        const lib = createContext();
+        const original = lib.createObject(
+            GeometryClass.BoxGeometry,
+            MaterialClass.MeshBasicMaterial
+        );
+        const clone = lib.cloneObject(original);
+        expect(meshMaterial(lib, clone)).toBe(meshMaterial(lib, original));
+
+        expect(lib.setObjectOpacity(clone, 0.5)).toBe(0);
+        expect(meshMaterial(lib, clone)).not.toBe(meshMaterial(lib, original));
+        expect(meshMaterial(lib, clone).opacity).toBe(0.5);
+        expect(meshMaterial(lib, clone).transparent).toBe(true);
+        expect(meshMaterial(lib, original).opacity).toBe(1);
+    });
+
+    test('Keeps a material which is not shared', () => {
+        const lib = createContext();
+        const objectId = lib.createObject(
+            GeometryClass.BoxGeometry,
+            MaterialClass.MeshBasicMaterial
+        );
+        const material = meshMaterial(lib, objectId);
+
+        expect(lib.unshareMaterial(objectId)).toBe(0);
+        expect(lib.setObjectOpacity(objectId, 0.25)).toBe(0);
+        expect(meshMaterial(lib, objectId)).toBe(material);
+        expect(material.opacity).toBe(0.25);
+    });
+});
@@ -2,6 +2,15 @@
 // Copyright (c) 2025, Maciej Wasilewski
 
 import * as THREE from 'three';
+import { GLTFLoader } from 'three/examples/jsm/loaders/GLTFLoader.js';
+import { Line2 } from 'three/examples/jsm/lines/Line2.js';
+import { LineGeometry } from 'three/examples/jsm/lines/LineGeometry.js';
+import { LineMaterial } from 'three/examples/jsm/lines/LineMaterial.js';
+import { EffectComposer } from 'three/examples/jsm/postprocessing/EffectComposer.js';
+import { GTAOPass } from 'three/examples/jsm/postprocessing/GTAOPass.js';
+import { OutputPass } from 'three/examples/jsm/postprocessing/OutputPass.js';
+import { RenderPass } from 'three/examples/jsm/postprocessing/RenderPass.js';
+import { UnrealBloomPass } from 'three/examples/jsm/postprocessing/UnrealBloomPass.js';
 /**
  * Creates a context for the Three.js library, providing methods to create and manipulate 3D objects, textures, and scenes.
  */
@@ -10,18 +19,84 @@ export const createContext = () => {
     const __OBJECTS = new Map<number, THREE.Object3D>();
     const __TEXTURES = new Map<number, THREE.Texture>();
     const __LOADED_TEXTURES = new Set<string>();
+    const __CAMERAS = new Map<number, THREE.PerspectiveCamera | THREE.OrthographicCamera>();
+    /** Render targets, by the ID of their texture in __TEXTURES */
+    const __RENDER_TARGETS = new Map<number, THREE.WebGLRenderTarget>();
+    /** Materials of fat lines, which need to know the canvas resolution */
+    const __LINE_MATERIALS = new Set<LineMaterial>();
+    /** IDs of objects which face the camera, see setBillboard */
+    const __BILLBOARDS = new Set<number>();
+    const __MIXERS = new Map<number, THREE.AnimationMixer>();
+    /** Sprite atlases; the frames are UV rects, filled in once the JSON is loaded */
+    const __ATLASES = new Map<number, { texture?: THREE.Texture, frames: Map<string, THREE.Vector4>, ready: boolean }>();
+    const __SOUNDS = new Map<number, { buffer?: AudioBuffer, audio?: THREE.Audio, positional?: THREE.PositionalAudio }>();
+    /** Events for pollEvent as [kind, argument] pairs, oldest first */
+    const __EVENTS: [EventKind, number][] = [];
+    const MAX_EVENTS = 1024;
 
     /** COUNTERS */
     let __nextObjId = 0;
+    let __nextTextureId = 0;
+    let __nextSoundId = 0;
+    let __nextAtlasId = 0;
+    /** Camera 0 is the default camera created by `init` */
+    let __nextCameraId = 1;
+    let __activeCameraId = 0;
 
     /** THREE.JS MAIN OBJECTS */
-    let __camera: THREE.PerspectiveCamera;
+    let __camera: THREE.PerspectiveCamera | THREE.OrthographicCamera;
     let __scene: THREE.Scene;
     let __renderer: THREE.WebGLRenderer;
+    let __target: HTMLElement | undefined;
+    let __antialias = true;
+
+    /** POST-PROCESSING */
+    /** Settings of the ambient occlusion pass, undefined while it is off */
+    let __ssao: { radius: number, intensity: number } | undefined;
+    /** Settings of the bloom pass, undefined while it is off */
+    let __bloom: { strength: number, radius: number, threshold: number } | undefined;
+    /** Replaces the plain renderer in `render` while any post-processing is on */
+    let __composer: EffectComposer | undefined;
+
+    /** WASM MEMORY */
+    let __memory: WebAssembly.Memory | undefined;
+    let __wasmExports: WebAssembly.Exports | undefined;
+
+    /** TIME */
+    const __clock = new THREE.Clock(false);
+    let __lastRenderTime = 0;
+    /** Set by pauseLoop; the frame callback waiting for resumeLoop, if any */
+    let __paused = false;
+    let __pausedCallback: number | undefined;
+    /** Makes getDeltaTime report 0 until the first render after resumeLoop */
+    let __skipDelta = false;
+    /** Time between the last two renders in seconds, 0 until there were two */
+    let __lastFrameTime = 0;
+
+    /** RAYCASTING */
+    const raycaster = new THREE.Raycaster();
+    /** Intersection points of the last raycast, by object ID */
+    const __lastRaycastPoints = new Map<number, THREE.Vector3>();
 
     /** LOADERS */
     const utf8Decoder = new TextDecoder("utf-8");
     const textureLoader = new THREE.TextureLoader();
+    const cubeTextureLoader = new THREE.CubeTextureLoader();
+    const audioLoader = new THREE.AudioLoader();
+    const gltfLoader = new GLTFLoader();
+    const utf8Encoder = new TextEncoder();
+
+    /** AUDIO */
+    let __audioListener: THREE.AudioListener | undefined;
+
+    /** The listener is created lazily, as browsers only allow audio after a user gesture. */
+    function getAudioListener(): THREE.AudioListener {
+        if (!__audioListener) {
+            __audioListener = new THREE.AudioListener();
+            __camera?.add(__audioListener);
+        }
+        return __audioListener;
+    }
 
     /** INPUT HANDLING */
 
@@ -32,6 +107,22 @@ export const createContext = () => {
     let mouseMovementX: number = 0;
     let mouseMovementY: number = 0;
 
+    /** Bitmask, same layout as MouseEvent.buttons */
+    let mouseButtons: number = 0;
+
+    /** Text typed since the last getTypedText, at most MAX_TYPED_CHARS code points */
+    let typedText: string[] = [];
+    const MAX_TYPED_CHARS = 256;
+    /** Hidden input kept focused by setImeCapture, as IME text only reaches editable elements */
+    let __imeInput: HTMLInputElement | undefined;
+
+    /** Vertical wheel scrolling in pixels since the last getScrollDelta */
+    let scrollDelta: number = 0;
+
+    /** Absolute mouse position in pixels, relative to the canvas */
+    let mousePositionX: number = 0;
+    let mousePositionY: number = 0;
+
     function packU16sToU32(low: number, high: number) {
         return (high << 16) | (low & 0xFFFF);
     }
@@ -39,6 +130,22 @@ export const createContext = () => {
     function onMouseMove(ev: MouseEvent) {
         mouseMovementX += ev.movementX;
         mouseMovementY += ev.movementY;
+
+        const rect = __renderer?.domElement.getBoundingClientRect();
+        mousePositionX = ev.clientX - (rect?.left ?? 0);
+        mousePositionY = ev.clientY - (rect?.top ?? 0);
+    }
+
+    function onMouseButtons(ev: MouseEvent) {
+        mouseButtons = ev.buttons;
+    }
+
+    function onWheel(ev: WheelEvent) {
+        // some browsers and devices report lines or pages instead of pixels
+        const scale = ev.deltaMode === WheelEvent.DOM_DELTA_LINE ? 16
+            : ev.deltaMode === WheelEvent.DOM_DELTA_PAGE ? window.innerHeight
+            : 1;
+        scrollDelta += ev.deltaY * scale;
     }
 
     function onKeyUp(ev: KeyboardEvent) {
@@ -55,12 +162,130 @@ export const createContext = () => {
         if (pressed) {
             keysPressed |= pressed;
         }
+        onTextInput(ev);
+    }
+
+    function onTextInput(ev: KeyboardEvent) {
+        if (ev.ctrlKey || ev.metaKey || ev.isComposing) {
+            return;
+        }
+        // printable keys are named by the single character they produce
+        const text = ev.key === 'Enter' ? '\n'
+            : ev.key === 'Backspace' ? '\b'
+            : [...ev.key].length === 1 ? ev.key
+            : undefined;
+        if (text !== undefined) {
+            pushTypedText(text);
+        }
+    }
+
+    function pushTypedText(text: string) {
+        typedText.push(...text);
+        if (typedText.length > MAX_TYPED_CHARS) {
+            typedText.splice(0, typedText.length - MAX_TYPED_CHARS);
+        }
+    }
+
+    function focusImeInput() {
+        __imeInput?.focus();
+    }
+
+    /**
+     * Captures text composed with an IME (e.g. for Chinese or Japanese) for getTypedText. Browsers
+     * only deliver it to editable elements, so while enabled a hidden input inside the target element
+     * is focused, and focused again whenever the target is clicked.
+     * @param enabled - 1 to start capturing, 0 to stop and remove the input.
+     * @returns 0, or ErrorCode.NotInitialized if called before init.
+     */
+    function setImeCapture(enabled: number): number {
+        if (!__target) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        if (enabled && !__imeInput) {
+            const input = document.createElement('input');
+            input.autocomplete = 'off';
+            input.setAttribute('aria-hidden', 'true');
+            Object.assign(input.style, { position: 'absolute', width: '1px', height: '1px', opacity: '0', pointerEvents: 'none' });
+            input.addEventListener('compositionend', (ev) => {
+                pushTypedText(ev.data);
+                input.value = '';
+            });
+            // keys typed without composition are taken from keydown already
+            input.addEventListener('input', (ev) => {
+                if (!(ev as InputEvent).isComposing) {
+                    input.value = '';
+                }
+            });
+            __target.appendChild(input);
+            __target.addEventListener('pointerdown', focusImeInput);
+            __imeInput = input;
+            input.focus();
+        } else if (!enabled && __imeInput) {
+            __target.removeEventListener('pointerdown', focusImeInput);
+            __imeInput.remove();
+            __imeInput = undefined;
+        }
+        return 0;
+    }
+
+    /**
+     * Writes the UTF-8 encoded text typed since the last call into WASM memory and clears it.
+     * Enter is reported as "\n" and Backspace as "\b".
+     * @param outPtr - Pointer to the output buffer.
+     * @param maxLen - Size of the output buffer in bytes. Nothing is written (or cleared) if the text does not fit.
+     * @returns The length of the text in bytes, or ErrorCode.NotInitialized if WASM memory is not bound.
+     */
+    function getTypedText(outPtr: number, maxLen: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const bytes = utf8Encoder.encode(typedText.join(''));
+        if (bytes.length <= maxLen) {
+            new Uint8Array(__memory.buffer, outPtr, bytes.length).set(bytes);
+            typedText = [];
+        }
+        return bytes.length;
+    }
+
+    function pushEvent(kind: EventKind, arg: number = 0) {
+        if (__EVENTS.length >= MAX_EVENTS) {
+            __EVENTS.shift();
+        }
+        __EVENTS.push([kind, arg]);
+    }
+
+    /**
+     * Dequeues the oldest event and writes it into WASM memory as two i32s (kind, argument).
+     * @param outPtr - Pointer to the output buffer.
+     * @returns 1 if an event was written, 0 if the queue is empty, or ErrorCode.NotInitialized if WASM memory is not bound.
+     */
+    function pollEvent(outPtr: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+        const event = __EVENTS.shift();
+        if (!event) {
+            return 0;
+        }
+
+        new Int32Array(__memory.buffer, outPtr, 2).set(event);
+        return 1;
     }
 
     function initInputListeners() {
         window.addEventListener('mousemove', onMouseMove);
+        window.addEventListener('mousedown', onMouseButtons);
+        window.addEventListener('mouseup', onMouseButtons);
+        window.addEventListener('wheel', onWheel, { passive: true });
         window.addEventListener('keydown', onKeyDown);
         window.addEventListener('keyup', onKeyUp);
+        window.addEventListener('blur', () => pushEvent(EventKind.WindowBlur));
+        window.addEventListener('focus', () => pushEvent(EventKind.WindowFocus));
     }
 
 
@@ -82,12 +307,134 @@ export const createContext = () => {
         return ret;
     }
 
+    /**
+     * Returns the vertical scrolling in pixels since the last call to this function, positive
+     * when scrolling down. This function resets the value to zero after retrieving it.
+     */
+    function getScrollDelta(): number {
+        const ret = scrollDelta;
+        scrollDelta = 0;
+        return ret;
+    }
+
+    /**
+     * This function is not meant to be called from WASM.
+     *
+     * Binds the memory of the instantiated WASM module so that bindings taking
+     * pointers can read from (and write to) it. Call it right after instantiation.
+     */
+    function bindMemory(memory: WebAssembly.Memory) {
+        __memory = memory;
+    }
+
+    /**
+     * This function is not meant to be called from WASM.
+     *
+     * Binds the instantiated WASM module: its memory (see `bindMemory`) and its exports,
+     * which are needed by bindings calling back into WASM such as `requestFrame`.
+     */
+    function bindInstance(instance: WebAssembly.Instance) {
+        __wasmExports = instance.exports;
+        if (instance.exports.memory instanceof WebAssembly.Memory) {
+            bindMemory(instance.exports.memory);
+        }
+    }
+
+    function readF32s(ptr: number, len: number): Float32Array {
+        return new Float32Array(__memory!.buffer, ptr, len);
+    }
+
+    function writeF32s(ptr: number, values: ArrayLike<number>) {
+        new Float32Array(__memory!.buffer, ptr, values.length).set(values);
+    }
+
+    function readString(ptr: number, len: number): string {
+        return utf8Decoder.decode(new Uint8Array(__memory!.buffer, ptr, len));
+    }
+
+    /**
+     * Returns the material of the object with the given ID, or an ErrorCode if
+     * the object does not exist or has no (single) material.
+     */
+    function getMaterial(id: number): THREE.Material | ErrorCode {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        const material = (object as THREE.Mesh).material;
+        if (!(material instanceof THREE.Material)) {
+            console.error(`Object with ID ${id} has no material.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        return material;
+    }
+
+    /**
+     * Returns the currently held mouse buttons as a bitmask (1 = left, 2 = right, 4 = middle).
+     */
+    function getMouseButtons(): number {
+        return mouseButtons;
+    }
+
+    /**
+     * Returns the mouse position in pixels relative to the canvas.
+     * The value is packed the same way as in `getMouseMovement`.
+     */
+    function getMousePosition(): number {
+        return packU16sToU32(mousePositionX, mousePositionY);
+    }
+
+    /**
+     * Returns the ID of the given object or of its closest ancestor which has one, or -1.
+     */
+    function findObjectId(object: THREE.Object3D | null): number {
+        for (; object; object = object.parent) {
+            for (const [id, candidate] of __OBJECTS) {
+                if (candidate === object) {
+                    return id;
+                }
+            }
+        }
+        return -1;
+    }
+
+    /**
+     * Returns the pressed buttons of the gamepad at the given index as a bitmask
+     * (bit n is `Gamepad.buttons[n]`), or -1 if no gamepad is connected at that index.
+     */
+    function getGamepadState(index: number): number {
+        const gamepad = navigator.getGamepads?.()[index];
+        if (!gamepad) {
+            return -1;
+        }
+
+        let state = 0;
+        gamepad.buttons.forEach((button, i) => {
+            if (button.pressed) {
+                state |= 1 << i;
+            }
+        });
+        return state;
+    }
+
+    /**
+     * Returns the value of an axis of the gamepad at the given index, between -1 and 1.
+     * Returns 0 if the gamepad or the axis does not exist.
+     */
+    function getGamepadAxis(index: number, axis: number): number {
+        const gamepad = navigator.getGamepads?.()[index];
+        return gamepad?.axes[axis] ?? 0;
+    }
+
     /* BINDINGS */
 
     /** Initialized a THREE.Mesh object. It is not added to scene by default.
      * @param geometry - The type of geometry to use for the object, specified by GeometryClass enum.
      * @param material - The type of material to use for the object, specified by MaterialClass enum.
-     * @returns The ID of the created object, or -1 if an error occurred.
+     * @returns The ID of the created object, or a negative ErrorCode if an error occurred.
      */
     function createObject(geometry: GeometryClass, material: MaterialClass): number {
         const geometryClass = GeometryClass[geometry];
@@ -109,7 +456,49 @@ export const createContext = () => {
             return id;
         } catch (error) {
             console.error(`Error creating object: ${error}`);
-            return -1;
+            return ErrorCode.JsException;
+        }
+    }
+
+    /**
+     * Same as `createObject`, but passes the given parameters to the geometry constructor.
+     * NaN parameters are passed as `undefined` so that THREE.js applies its defaults.
+     * @param geometry - The type of geometry to use for the object, specified by GeometryClass enum.
+     * @param material - The type of material to use for the object, specified by MaterialClass enum.
+     * @param paramsPtr - Pointer to an f32 buffer in WASM memory holding the constructor parameters.
+     * @param paramsLen - Number of floats in the buffer.
+     * @returns The ID of the created object, or a negative ErrorCode if an error occurred.
+     */
+    function createObjectParameterized(
+        geometry: GeometryClass,
+        material: MaterialClass,
+        paramsPtr: number,
+        paramsLen: number,
+    ): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const params = Array.from(readF32s(paramsPtr, paramsLen), (v) => Number.isNaN(v) ? undefined : v);
+
+        // @ts-ignore
+        let geometryClassInstance = THREE[GeometryClass[geometry]];
+        // @ts-ignore
+        let materialClassInstance = THREE[MaterialClass[material]];
+
+        try {
+            const geometry: THREE.BufferGeometry = new geometryClassInstance(...params);
+            const material: THREE.Material = new materialClassInstance({ color: 0xffffff });
+            const mesh: THREE.Object3D = new THREE.Mesh(geometry, material);
+
+            const id = __nextObjId;
+            __OBJECTS.set(__nextObjId, mesh);
+            __nextObjId++;
+            return id;
+        } catch (error) {
+            console.error(`Error creating object: ${error}`);
+            return ErrorCode.JsException;
         }
     }
 
@@ -156,7 +545,7 @@ export const createContext = () => {
 
         if (!texture) {
             console.error(`Texture ${textureId} not found.`);
-            return -1;
+            return ErrorCode.UnknownObject;
         }
 
         if (texture.userData.rows || texture.userData.columns) {
@@ -184,7 +573,7 @@ export const createContext = () => {
      * @param x - The x-coordinate of the position.
      * @param y - The y-coordinate of the position.
      * @param z - The z-coordinate of the position.
-     * @returns 0 if the position was set successfully, or -1 if the object with the given ID was not found.
+     * @returns 0 if the position was set successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
      */
     function setPosition(
         id: number,
@@ -195,7 +584,7 @@ export const createContext = () => {
         const object = __OBJECTS.get(id);
         if (!object) {
             console.error(`Object with ID ${id} not found.`);
-            return -1;
+            return ErrorCode.UnknownObject;
         }
 
         object.position.set(x, y, z);
@@ -208,7 +597,7 @@ export const createContext = () => {
      * @param x - The x-component of the rotation (in radians).
      * @param y - The y-component of the rotation (in radians).
      * @param z - The z-component of the rotation (in radians).
-     * @returns 0 if the rotation was set successfully, or -1 if the object with the given ID was not found.
+     * @returns 0 if the rotation was set successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
      */
     function setRotation(
         id: number,
@@ -219,20 +608,90 @@ export const createContext = () => {
         const object = __OBJECTS.get(id);
         if (!object) {
             console.error(`Object with ID ${id} not found.`);
-            return -1;
+            return ErrorCode.UnknownObject;
         }
 
         object.rotation.set(x, y, z);
         return 0;
     }
 
+    /**
+     * Sets the position, rotation and scale of an object in the 3D scene in a single call.
+     * @param id - The unique ID of the object to set the transform for.
+     * @returns 0 if the transform was set successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
+     */
+    function setTransform(
+        id: number,
+        px: number, py: number, pz: number,
+        rx: number, ry: number, rz: number,
+        sx: number, sy: number, sz: number,
+    ): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        object.position.set(px, py, pz);
+        object.rotation.set(rx, ry, rz);
+        object.scale.set(sx, sy, sz);
+        return 0;
+    }
+
+    const __matrix = new THREE.Matrix4();
+
+    /**
+     * Sets the local transform of an object from a matrix. The matrix is decomposed
+     * into position, rotation and scale.
+     * @param id - The unique ID of the object to set the transform for.
+     * @param ptr - Pointer to 16 floats in WASM memory, in column-major order.
+     * @returns 0 if the transform was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMatrix(id: number, ptr: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        __matrix.fromArray(readF32s(ptr, 16));
+        __matrix.decompose(object.position, object.quaternion, object.scale);
+        return 0;
+    }
+
+    /**
+     * Sets the rotation of an object in the 3D scene from a quaternion.
+     * @param id - The unique ID of the object to set the rotation for.
+     * @returns 0 if the rotation was set successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
+     */
+    function setQuaternion(
+        id: number,
+        x: number,
+        y: number,
+        z: number,
+        w: number,
+    ): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        object.quaternion.set(x, y, z, w);
+        return 0;
+    }
+
     /**
      * Sets the scale of an object in the 3D scene.
      * @param id - The unique ID of the object to set the scale for.
      * @param x - The x-component of the scale.
      * @param y - The y-component of the scale.
      * @param z - The z-component of the scale.
-     * @return 0 if the scale was set successfully, or -1 if the object with the given ID was not found.
+     * @return 0 if the scale was set successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
      */
     function setScale(
         id: number,
@@ -243,7 +702,7 @@ export const createContext = () => {
         const object = __OBJECTS.get(id);
         if (!object) {
             console.error(`Object with ID ${id} not found.`);
-            return -1;
+            return ErrorCode.UnknownObject;
         }
 
         object.scale.set(x, y, z);
@@ -254,7 +713,7 @@ export const createContext = () => {
         const object = __OBJECTS.get(id);
         if (!object) {
             console.error(`Object with ID ${id} not found.`);
-            return -1;
+            return ErrorCode.UnknownObject;
         }
 
         __scene.add(object);
@@ -264,19 +723,135 @@ export const createContext = () => {
     /**
      * Removes an object from the 3D scene.
      * @param id - The unique ID of the object to remove.
-     * @returns 0 if the object was removed successfully, or -1 if the object with the given ID was not found.
+     * @returns 0 if the object was removed successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
      */
     function removeObjectFromScene(id: number): number {
         const object = __OBJECTS.get(id);
         if (!object) {
             console.error(`Object with ID ${id} not found.`);
-            return -1;
+            return ErrorCode.UnknownObject;
         }
 
         __scene.remove(object);
         return 0;
     }
 
+    /**
+     * Removes every object from the 3D scene, including lights.
+     * @param dispose - 1 to also free the removed objects like disposeObject, 0 to keep them alive.
+     * @returns 0 if the scene was cleared successfully, or ErrorCode.NotInitialized if the scene is not initialized.
+     */
+    function clearScene(dispose: number): number {
+        if (!__scene) {
+            console.error("Scene is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        for (const object of [...__scene.children]) {
+            const id = findObjectId(object);
+            if (dispose && id >= 0) {
+                disposeObject(id);
+            } else {
+                __scene.remove(object);
+            }
+        }
+        return 0;
+    }
+
+    /**
+     * Removes an object from its parent and frees its GPU resources (geometry, material and
+     * sprite textures). Textures shared through `__TEXTURES` are not disposed.
+     * The ID is invalid afterwards.
+     * @param id - The unique ID of the object to dispose.
+     * @returns 0 if the object was disposed successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
+     */
+    function disposeObject(id: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        object.removeFromParent();
+        __MIXERS.get(id)?.stopAllAction();
+        __MIXERS.delete(id);
+        __BILLBOARDS.delete(id);
+        object.traverse((child) => {
+            if (child instanceof THREE.Sprite) {
+                // sprites own a clone of their texture
+                child.material.map?.dispose();
+            }
+            if ('geometry' in child && child.geometry instanceof THREE.BufferGeometry) {
+                child.geometry.dispose();
+            }
+            if ('material' in child && child.material instanceof THREE.Material) {
+                if (child.material instanceof LineMaterial) {
+                    __LINE_MATERIALS.delete(child.material);
+                }
+                child.material.dispose();
+            }
+        });
+
+        __OBJECTS.delete(id);
+        return 0;
+    }
+
+    function cloneWith(id: number, cloneMaterial: (material: THREE.Material) => THREE.Material): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        const clone = object.clone();
+        clone.traverse((child) => {
+            if ('material' in child && child.material instanceof THREE.Material) {
+                child.material = cloneMaterial(child.material);
+            }
+        });
+        if (__BILLBOARDS.has(id)) {
+            __BILLBOARDS.add(__nextObjId);
+        }
+
+        const cloneId = __nextObjId;
+        __OBJECTS.set(__nextObjId, clone);
+        __nextObjId++;
+        return cloneId;
+    }
+
+    /**
+     * Clones an object with its transform and children. It is not added to scene by default.
+     * The clone shares geometry and material with the original.
+     * @param id - The unique ID of the object to clone.
+     * @returns The ID of the clone, or ErrorCode.UnknownObject if the object was not found.
+     */
+    function cloneObject(id: number): number {
+        return cloneWith(id, (material) => material);
+    }
+
+    /**
+     * Clones an object like cloneObject, but gives the clone its own copies of the materials.
+     * Geometry is still shared.
+     * @param id - The unique ID of the object to clone.
+     * @returns The ID of the clone, or ErrorCode.UnknownObject if the object was not found.
+     */
+    function cloneObjectDeep(id: number): number {
+        return cloneWith(id, copyMaterial);
+    }
+
+    /** Copies a material, including the state the bindings keep for some material types. */
+    function copyMaterial(material: THREE.Material): THREE.Material {
+        const copy = material.clone();
+        if (copy instanceof THREE.SpriteMaterial && copy.map) {
+            // sprites own their texture, which disposeObject frees
+            copy.map = copy.map.clone();
+        }
+        if (copy instanceof LineMaterial) {
+            __LINE_MATERIALS.add(copy);
+        }
+        return copy;
+    }
+
     /**
      * Sets the offset for a sprite animation frame.
      * This function updates the texture offset of a sprite to display a specific frame
@@ -286,7 +861,7 @@ export const createContext = () => {
      * @param id - The unique ID of the sprite object.
      * @param frameX - The x-coordinate of the frame in the grid (e.g. if given texture contains 10 sprites in each row then this value can be between 0 and 9).
      * @param frameY - The y-coordinate of the frame in the grid (e.g. if given texture contains 10 sprites in each column then this value can be between 0 and 9)
-     * @returns 0 if the offset was set successfully, or -1 if the sprite with the given ID was not found or has no texture.
+     * @returns 0 if the offset was set successfully, or a negative ErrorCode if the sprite with the given ID was not found or has no (animated) texture.
      */
     function setSpriteAnimationOffset(
         id: number,
@@ -294,22 +869,26 @@ export const createContext = () => {
         frameY: number
     ): number {
         const object = __OBJECTS.get(id);
-        if (!object || !(object instanceof THREE.Sprite)) {
-            console.error(`Object with ID ${id} is not a sprite or not found.`);
-            return -1;
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(object instanceof THREE.Sprite)) {
+            console.error(`Object with ID ${id} is not a sprite.`);
+            return ErrorCode.InvalidArgument;
         }
 
         const texture = object.material.map;
         if (!texture) {
             console.error(`Sprite with ID ${id} has no texture.`);
-            return -1;
+            return ErrorCode.InvalidArgument;
         }
 
         const { rows, columns } = texture.userData;
 
         if (!rows || !columns) {
             console.error(`Texture of Object ${id} is not animated (has no rows/cols)`)
-            return -1;
+            return ErrorCode.InvalidArgument;
         }
 
         texture.offset.set(frameX / columns, frameY / rows);
@@ -317,6 +896,125 @@ export const createContext = () => {
         return 0;
     }
 
+    type AtlasFrame = { frame: { x: number, y: number, w: number, h: number }, rotated?: boolean };
+
+    /**
+     * Loads a TexturePacker-style JSON atlas ("JSON (Hash)" or "JSON (Array)") and the image it
+     * refers to, which is resolved relative to the JSON URL. Rotated frames are skipped.
+     * @param urlPtr - Pointer to the URL of the JSON file in WASM memory.
+     * @param urlLen - Length of the URL in bytes.
+     * @returns The ID of the atlas, or ErrorCode.NotInitialized if WASM memory is not bound.
+     */
+    function loadAtlas(urlPtr: number, urlLen: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const path = readString(urlPtr, urlLen);
+        const atlas: { texture?: THREE.Texture, frames: Map<string, THREE.Vector4>, ready: boolean } = {
+            frames: new Map(),
+            ready: false,
+        };
+        const id = __nextAtlasId;
+        __ATLASES.set(id, atlas);
+        __nextAtlasId++;
+
+        fetch(path)
+            .then((response) => response.json())
+            .then((json: {
+                frames: Record<string, AtlasFrame> | (AtlasFrame & { filename: string })[],
+                meta: { image: string, size: { w: number, h: number } },
+            }) => {
+                const { w, h } = json.meta.size;
+                const entries: [string, AtlasFrame][] = Array.isArray(json.frames)
+                    ? json.frames.map((frame) => [frame.filename, frame])
+                    : Object.entries(json.frames);
+                for (const [name, { frame, rotated }] of entries) {
+                    if (rotated) {
+                        console.warn(`Skipping rotated frame ${name} of atlas ${path}.`);
+                        continue;
+                    }
+                    // UVs start at the bottom-left corner, the JSON at the top-left one
+                    atlas.frames.set(name, new THREE.Vector4(frame.x / w, 1 - (frame.y + frame.h) / h, frame.w / w, frame.h / h));
+                }
+
+                const image = new URL(json.meta.image, new URL(path, document.baseURI)).href;
+                atlas.texture = textureLoader.load(
+                    image,
+                    () => { atlas.ready = true; },
+                    undefined,
+                    (error) => console.error(`Error loading atlas image ${image}: ${error}`),
+                );
+            })
+            .catch((error) => console.error(`Error loading atlas ${path}: ${error}`));
+
+        return id;
+    }
+
+    /**
+     * @returns 1 if the JSON and the image of the atlas finished loading, 0 otherwise.
+     */
+    function atlasIsReady(id: number): number {
+        return __ATLASES.get(id)?.ready ? 1 : 0;
+    }
+
+    /**
+     * Writes the UV rect (x, y, width, height) of an atlas frame into WASM memory.
+     * @param outPtr - Pointer to a buffer of 4 floats.
+     * @returns 0 if the rect was written, ErrorCode.UnknownObject if the atlas was not found, or ErrorCode.InvalidArgument if it has no such frame (yet).
+     */
+    function getAtlasFrame(atlasId: number, namePtr: number, nameLen: number, outPtr: number): number {
+        const atlas = __ATLASES.get(atlasId);
+        if (!atlas || !__memory) {
+            console.error(`Atlas ${atlasId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        const frame = atlas.frames.get(readString(namePtr, nameLen));
+        if (!frame) {
+            return ErrorCode.InvalidArgument;
+        }
+
+        writeF32s(outPtr, frame.toArray());
+        return 0;
+    }
+
+    /**
+     * Shows a named frame of an atlas on a sprite, replacing the sprite's texture with the atlas
+     * texture if needed.
+     * @returns 0 if the frame was set, or a negative ErrorCode if the sprite or atlas was not found, the atlas is not loaded or has no such frame.
+     */
+    function setSpriteFrame(id: number, atlasId: number, namePtr: number, nameLen: number): number {
+        const object = __OBJECTS.get(id);
+        const atlas = __ATLASES.get(atlasId);
+        if (!object || !atlas) {
+            console.error(`Object with ID ${id} or atlas ${atlasId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(object instanceof THREE.Sprite)) {
+            console.error(`Object with ID ${id} is not a sprite.`);
+            return ErrorCode.InvalidArgument;
+        }
+        const name = readString(namePtr, nameLen);
+        const frame = atlas.frames.get(name);
+        if (!atlas.texture || !frame) {
+            console.error(`Atlas ${atlasId} has no frame ${name} (or is not loaded yet).`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        let map = object.material.map;
+        if (!map || map.source !== atlas.texture.source) {
+            // like createSprite, every sprite owns a clone so that frames are independent
+            map?.dispose();
+            map = atlas.texture.clone();
+            object.material.map = map;
+            object.material.needsUpdate = true;
+        }
+        map.offset.set(frame.x, frame.y);
+        map.repeat.set(frame.z, frame.w);
+        return 0;
+    }
+
     /** TODO: docs */
     /**
      * Sets the position of the camera in the 3D scene.
@@ -324,13 +1022,13 @@ export const createContext = () => {
      * @param x - The x-coordinate of the camera's position.
      * @param y - The y-coordinate of the camera's position.
      * @param z - The z-coordinate of the camera's position.
-     * @returns 0 if the position was set successfully, or -1 if the camera is not initialized.
+     * @returns 0 if the position was set successfully, or ErrorCode.NotInitialized if the camera is not initialized.
      */
     function setCameraPosition(
         x: number, y: number, z: number,
     ): number {
         if (!__camera) {
-            return -1
+            return ErrorCode.NotInitialized;
         }
 
         __camera.position.set(x, y, z)
@@ -338,86 +1036,2700 @@ export const createContext = () => {
         return 0;
     }
 
+    /**
+     * Sets the position of the camera with the given ID, which does not have to be the active one.
+     * @returns 0 if the position was set successfully, or ErrorCode.UnknownObject if the camera was not found.
+     */
+    function setCameraPositionFor(cameraId: number, x: number, y: number, z: number): number {
+        const camera = __CAMERAS.get(cameraId);
+        if (!camera) {
+            console.error(`Camera ${cameraId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        camera.position.set(x, y, z);
+        return 0;
+    }
+
+
+    /**
+     * Sets the camera to look at a specific point in the 3D scene.
+     * 
+     * @param x - The x-coordinate of the point to look at.
+     * @param y - The y-coordinate of the point to look at.
+     * @param z - The z-coordinate of the point to look at.
+     * @returns 0 if the camera was set to look at the point successfully, or ErrorCode.NotInitialized if the camera is not initialized.
+     */
+    function cameraLookAt(
+        x: number, y: number, z: number,
+    ) {
+        if (!__camera) {
+            return ErrorCode.NotInitialized;
+        }
+
+        __camera.lookAt(x, y, z);
+
+        return 0;
+    }
+
+    /**
+     * Points the camera with the given ID at a point, like cameraLookAt.
+     * @returns 0 if the camera was rotated successfully, or ErrorCode.UnknownObject if the camera was not found.
+     */
+    function cameraLookAtFor(cameraId: number, x: number, y: number, z: number): number {
+        const camera = __CAMERAS.get(cameraId);
+        if (!camera) {
+            console.error(`Camera ${cameraId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        camera.lookAt(x, y, z);
+        return 0;
+    }
+
+    /**
+     * Moves the object to a single render layer. An object is rendered by a camera only if their layer masks intersect.
+     * @param layer - Index of the layer, between 0 and 31.
+     * @returns 0 if the layer was set, ErrorCode.UnknownObject if the object was not found, or ErrorCode.InvalidArgument if the index is out of range.
+     */
+    function setObjectLayer(id: number, layer: number): number {
+        const obj = __OBJECTS.get(id);
+        if (!obj) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (layer < 0 || layer > 31) {
+            console.error(`Layer ${layer} is out of range.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        obj.layers.set(layer);
+        return 0;
+    }
+
+    /**
+     * Sets the layer mask of the camera with the given ID, which does not have to be the active one.
+     * @param mask - Bitmask of the layers the camera renders.
+     * @returns 0 if the mask was set, or ErrorCode.UnknownObject if the camera was not found.
+     */
+    function setCameraLayers(cameraId: number, mask: number): number {
+        const camera = __CAMERAS.get(cameraId);
+        if (!camera) {
+            console.error(`Camera ${cameraId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        camera.layers.mask = mask | 0;
+        return 0;
+    }
+
+    /**
+     * Makes the camera with the given ID the one used by render, raycasting and the camera setters.
+     * Camera 0 is the default camera created by `init`.
+     * @returns 0 if the camera was activated, or ErrorCode.UnknownObject if the camera was not found.
+     */
+    function setActiveCamera(cameraId: number): number {
+        const camera = __CAMERAS.get(cameraId);
+        if (!camera) {
+            console.error(`Camera ${cameraId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        if (__audioListener) {
+            camera.add(__audioListener);
+        }
+        __camera = camera;
+        __activeCameraId = cameraId;
+        return 0;
+    }
+
+    /**
+     * Sets the vertical field of view of the camera.
+     * This is a no-op if an orthographic camera is active.
+     *
+     * @param fov - The field of view in degrees.
+     * @returns 0 if the fov was set successfully, or ErrorCode.NotInitialized if the camera is not initialized.
+     */
+    function setCameraFov(fov: number): number {
+        if (!__camera) {
+            return ErrorCode.NotInitialized;
+        }
+
+        if (__camera instanceof THREE.PerspectiveCamera) {
+            __camera.fov = fov;
+            __camera.updateProjectionMatrix();
+        }
+
+        return 0;
+    }
+
+    /**
+     * Sets the aspect ratio of the camera.
+     * This is a no-op if an orthographic camera is active.
+     *
+     * @param aspect - The aspect ratio (width / height).
+     * @returns 0 if the aspect was set successfully, or ErrorCode.NotInitialized if the camera is not initialized.
+     */
+    function setCameraAspect(aspect: number): number {
+        if (!__camera) {
+            return ErrorCode.NotInitialized;
+        }
+
+        if (__camera instanceof THREE.PerspectiveCamera) {
+            __camera.aspect = aspect;
+            __camera.updateProjectionMatrix();
+        }
+
+        return 0;
+    }
+
+    /**
+     * Sets the near and far clipping planes of the camera.
+     *
+     * @returns 0 if the planes were set successfully, or ErrorCode.NotInitialized if the camera is not initialized.
+     */
+    function setCameraClip(near: number, far: number): number {
+        if (!__camera) {
+            return ErrorCode.NotInitialized;
+        }
+
+        __camera.near = near;
+        __camera.far = far;
+        __camera.updateProjectionMatrix();
+
+        return 0;
+    }
+
+    /**
+     * Replaces the active camera, keeping the position and orientation of the previous one.
+     */
+    function replaceCamera(camera: THREE.PerspectiveCamera | THREE.OrthographicCamera) {
+        camera.position.copy(__camera.position);
+        camera.quaternion.copy(__camera.quaternion);
+        __camera = camera;
+        __CAMERAS.set(__activeCameraId, camera);
+    }
+
+    /**
+     * Switches to an orthographic camera.
+     *
+     * @returns 0 if the camera was switched successfully, or ErrorCode.NotInitialized if the camera is not initialized.
+     */
+    function useOrthographicCamera(
+        left: number, right: number, top: number, bottom: number, near: number, far: number,
+    ): number {
+        if (!__camera) {
+            return ErrorCode.NotInitialized;
+        }
+
+        replaceCamera(new THREE.OrthographicCamera(left, right, top, bottom, near, far));
+        return 0;
+    }
+
+    /**
+     * Switches to a perspective camera.
+     *
+     * @returns 0 if the camera was switched successfully, or ErrorCode.NotInitialized if the camera is not initialized.
+     */
+    function usePerspectiveCamera(fov: number, aspect: number, near: number, far: number): number {
+        if (!__camera) {
+            return ErrorCode.NotInitialized;
+        }
+
+        replaceCamera(new THREE.PerspectiveCamera(fov, aspect, near, far));
+        return 0;
+    }
+
+    /**
+     * Initializes the Three.js context with a renderer, scene, and camera.
+     * @param target - The HTML element to attach the renderer's canvas to.
+     */
+    function init(
+        target: HTMLElement,
+    ) {
+        __camera = new THREE.PerspectiveCamera(75, target.clientWidth / target.clientHeight, 0.1, 1000);
+        __CAMERAS.set(0, __camera);
+        __scene = new THREE.Scene();
+        __renderer = new THREE.WebGLRenderer({ antialias: __antialias });
+
+        __renderer.setSize(target.clientWidth, target.clientHeight);
+        target.appendChild(__renderer.domElement);
+        __target = target;
+
+        __clock.start();
+        __lastRenderTime = 0;
+    }
+
+    /** 
+     * Sets the background color of the scene.
+     */
+    function setBg(color: number) {
+        if (!__scene) {
+            console.error("Scene is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        __scene.background = new THREE.Color(color);
+        return 0;
+    }
+
+    function render() {
+        if (!__renderer || !__scene || !__camera) {
+            console.error("Renderer, scene, or camera is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const now = __clock.getElapsedTime();
+        __skipDelta = false;
+        for (const mixer of __MIXERS.values()) {
+            mixer.update(now - __lastRenderTime);
+        }
+        updateBillboards();
+
+        // post-processing renders several times per frame, so count them all
+        __renderer.info.autoReset = false;
+        __renderer.info.reset();
+        if (__composer) {
+            for (const pass of __composer.passes) {
+                if (pass instanceof RenderPass || pass instanceof GTAOPass) {
+                    pass.camera = __camera;
+                }
+            }
+            __composer.render(now - __lastRenderTime);
+        } else {
+            __renderer.render(__scene, __camera);
+        }
+        if (__lastRenderTime > 0) {
+            __lastFrameTime = now - __lastRenderTime;
+        }
+        __lastRenderTime = now;
+
+        return 0;
+    }
+
+    /**
+     * Schedules the exported `threejs4wasm_frame` WASM function to be called on every
+     * animation frame, for as long as it returns a non-zero value.
+     * @param callbackPtr - Opaque pointer passed back to `threejs4wasm_frame`.
+     * @returns 0 if the frame was requested, or ErrorCode.NotInitialized if the instance is not bound.
+     */
+    function requestFrame(callbackPtr: number): number {
+        const frame = __wasmExports?.threejs4wasm_frame;
+        if (typeof frame !== 'function') {
+            console.error("WASM instance is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        if (__paused) {
+            __pausedCallback = callbackPtr;
+            return 0;
+        }
+
+        requestAnimationFrame(() => {
+            if (__paused) {
+                __pausedCallback = callbackPtr;
+            } else if (frame(callbackPtr)) {
+                requestFrame(callbackPtr);
+            }
+        });
+        return 0;
+    }
+
+    /**
+     * Stops calling the frame callback scheduled by requestFrame until resumeLoop.
+     * @returns 0
+     */
+    function pauseLoop(): number {
+        __paused = true;
+        return 0;
+    }
+
+    /**
+     * Restarts a loop stopped by pauseLoop. The first frame afterwards gets a delta time of 0.
+     * @returns 0 if the loop was resumed (or was not paused), or a negative ErrorCode if the frame could not be requested.
+     */
+    function resumeLoop(): number {
+        if (!__paused) {
+            return 0;
+        }
+        __paused = false;
+        __lastRenderTime = __clock.getElapsedTime();
+        __skipDelta = true;
+
+        const callbackPtr = __pausedCallback;
+        __pausedCallback = undefined;
+        return callbackPtr === undefined ? 0 : requestFrame(callbackPtr);
+    }
+
+    /**
+     * @returns 1 if the loop is paused by pauseLoop, 0 otherwise.
+     */
+    function isPaused(): number {
+        return __paused ? 1 : 0;
+    }
+
+    /**
+     * Returns the time in seconds since the last call to `render`, or 0 right after resumeLoop.
+     */
+    function getDeltaTime(): number {
+        if (__skipDelta) {
+            return 0;
+        }
+        return __clock.getElapsedTime() - __lastRenderTime;
+    }
+
+    /**
+     * Returns the time in seconds since the context was initialized.
+     */
+    function getElapsedTime(): number {
+        return __clock.getElapsedTime();
+    }
+
+    /**
+     * Writes the stats of the last render into WASM memory as three i32s: the time since the
+     * frame before in microseconds, the number of draw calls and the number of triangles.
+     * @param outPtr - Pointer to the output buffer.
+     * @returns 0 if the stats were written, or ErrorCode.NotInitialized if the renderer or WASM memory is not initialized.
+     */
+    function getRenderStats(outPtr: number): number {
+        if (!__renderer || !__memory) {
+            console.error("Renderer or WASM memory is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const { calls, triangles } = __renderer.info.render;
+        new Int32Array(__memory.buffer, outPtr, 3).set([Math.round(__lastFrameTime * 1e6), calls, triangles]);
+        return 0;
+    }
+
+    /**
+     * Initializes a light. It is not added to scene by default.
+     * @param light - The type of light, specified by LightClass enum.
+     * @param color - The color of the light.
+     * @param intensity - The intensity of the light.
+     * @returns The ID of the created light, or a negative ErrorCode if an error occurred.
+     */
+    function createLight(light: LightClass, color: number, intensity: number): number {
+        const lightClass = LightClass[light];
+
+        // @ts-ignore
+        let lightClassInstance = THREE[lightClass];
+
+        try {
+            const object: THREE.Light = light === LightClass.HemisphereLight
+                // sky color, ground color, intensity
+                ? new THREE.HemisphereLight(color, color, intensity)
+                : new lightClassInstance(color, intensity);
+
+            const id = __nextObjId;
+            __OBJECTS.set(__nextObjId, object);
+            __nextObjId++;
+            return id;
+        } catch (error) {
+            console.error(`Error creating light: ${error}`);
+            return ErrorCode.JsException;
+        }
+    }
+
+    /**
+     * Sets the distance and decay of a point or spot light.
+     * @param id - The unique ID of the light.
+     * @param distance - Maximum range of the light. 0 means no limit.
+     * @param decay - The amount the light dims along the distance.
+     * @returns 0 if the params were set successfully, or a negative ErrorCode if the object is not a point or spot light.
+     */
+    function setLightParams(id: number, distance: number, decay: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(object instanceof THREE.PointLight || object instanceof THREE.SpotLight)) {
+            console.error(`Object with ID ${id} is not a point/spot light.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        object.distance = distance;
+        object.decay = decay;
+        return 0;
+    }
+
+    /**
+     * Sets the point a directional or spot light is aimed at.
+     * @param id - The unique ID of the light.
+     * @returns 0 if the target was set successfully, or a negative ErrorCode if the object is not a directional or spot light.
+     */
+    function setLightTarget(id: number, x: number, y: number, z: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(object instanceof THREE.DirectionalLight || object instanceof THREE.SpotLight)) {
+            console.error(`Object with ID ${id} is not a directional/spot light.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        object.target.position.set(x, y, z);
+        object.target.updateMatrixWorld();
+        return 0;
+    }
+
+    /**
+     * Sets the color of an object's material.
+     * @param id - The unique ID of the object.
+     * @param color - The new color.
+     * @returns 0 if the color was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMaterialColor(id: number, color: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        if (!('color' in material) || !(material.color instanceof THREE.Color)) {
+            console.error(`Material of object ${id} has no color.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.color.set(color);
+        return 0;
+    }
+
+    /**
+     * Sets the opacity of an object's material.
+     * @param id - The unique ID of the object.
+     * @param opacity - The opacity, between 0 and 1.
+     * @param transparent - 1 if the material should be rendered as transparent, 0 otherwise.
+     * @returns 0 if the opacity was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMaterialOpacity(id: number, opacity: number, transparent: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        material.opacity = opacity;
+        if (material.transparent !== (transparent !== 0)) {
+            material.transparent = transparent !== 0;
+            material.needsUpdate = true;
+        }
+        return 0;
+    }
+
+    /**
+     * Gives an object its own copy of its material if any other object uses the same material.
+     * @param id - The unique ID of the object.
+     * @returns 0 if the object's material is not shared (anymore), or a negative ErrorCode if the object was not found or has no material.
+     */
+    function unshareMaterial(id: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+        const object = __OBJECTS.get(id) as THREE.Mesh;
+
+        let shared = false;
+        for (const other of __OBJECTS.values()) {
+            other.traverse((child) => {
+                shared ||= child !== object && (child as THREE.Mesh).material === material;
+            });
+            if (shared) {
+                break;
+            }
+        }
+        if (shared) {
+            object.material = copyMaterial(material);
+        }
+        return 0;
+    }
+
+    /**
+     * Sets the opacity of an object, copying its material first if it is shared with other objects.
+     * @param id - The unique ID of the object.
+     * @param opacity - The opacity, between 0 and 1. Transparency is enabled below 1.
+     * @returns 0 if the opacity was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setObjectOpacity(id: number, opacity: number): number {
+        const code = unshareMaterial(id);
+        if (code !== 0) {
+            return code;
+        }
+        return setMaterialOpacity(id, opacity, opacity < 1 ? 1 : 0);
+    }
+
+    /**
+     * Sets the metalness and roughness of a MeshStandardMaterial (or MeshPhysicalMaterial).
+     * @param id - The unique ID of the object.
+     * @returns 0 if the parameters were set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMaterialPBR(id: number, metalness: number, roughness: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        if (!(material instanceof THREE.MeshStandardMaterial)) {
+            console.error(`Material of object ${id} is not a MeshStandardMaterial.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.metalness = metalness;
+        material.roughness = roughness;
+        return 0;
+    }
+
+    /**
+     * Sets how strongly a MeshStandardMaterial (or MeshPhysicalMaterial) reflects the environment map.
+     * @param id - The unique ID of the object.
+     * @param intensity - Strength of the reflections, 0 disables them.
+     * @returns 0 if the intensity was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setEnvMapIntensity(id: number, intensity: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        if (!(material instanceof THREE.MeshStandardMaterial)) {
+            console.error(`Material of object ${id} is not a MeshStandardMaterial.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.envMapIntensity = intensity;
+        return 0;
+    }
+
+    /**
+     * Sets the emissive color and intensity of an object's material.
+     * @param id - The unique ID of the object.
+     * @returns 0 if the emissive color was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMaterialEmissive(id: number, color: number, intensity: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        if (!('emissive' in material) || !(material.emissive instanceof THREE.Color)) {
+            console.error(`Material of object ${id} has no emissive color.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.emissive.set(color);
+        (material as THREE.MeshStandardMaterial).emissiveIntensity = intensity;
+        return 0;
+    }
+
+    /**
+     * Toggles wireframe rendering of an object's material.
+     * This is a no-op for materials without a wireframe mode (line and points materials).
+     * @param id - The unique ID of the object.
+     * @param enabled - 1 to render as wireframe, 0 to render normally.
+     * @returns 0 if the flag was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setWireframe(id: number, enabled: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        if ('wireframe' in material) {
+            material.wireframe = enabled !== 0;
+        }
+        return 0;
+    }
+
+    /**
+     * Sets which faces of an object's material are rendered.
+     * @param id - The unique ID of the object.
+     * @param side - THREE.FrontSide, THREE.BackSide or THREE.DoubleSide.
+     * @returns 0 if the side was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMaterialSide(id: number, side: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+        if (side !== THREE.FrontSide && side !== THREE.BackSide && side !== THREE.DoubleSide) {
+            console.error(`Unknown side ${side}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.side = side;
+        return 0;
+    }
+
+    /**
+     * Toggles the depth test of an object's material. Without it the object is drawn over
+     * everything which was drawn before it.
+     * @param id - The unique ID of the object.
+     * @param enabled - 1 to test against the depth buffer, 0 to always draw.
+     * @returns 0 if the flag was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setDepthTest(id: number, enabled: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        material.depthTest = enabled !== 0;
+        return 0;
+    }
+
+    /**
+     * Toggles depth writes of an object's material. Transparent objects usually look better
+     * without them, as they no longer hide what is drawn behind them later.
+     * @param id - The unique ID of the object.
+     * @param enabled - 1 to write to the depth buffer, 0 to only test against it.
+     * @returns 0 if the flag was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setDepthWrite(id: number, enabled: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        material.depthWrite = enabled !== 0;
+        return 0;
+    }
+
+    /**
+     * Sets the render order of an object. Objects with a higher order are drawn later.
+     * @returns 0 if the order was set successfully, or ErrorCode.UnknownObject if the object was not found.
+     */
+    function setRenderOrder(id: number, order: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        object.renderOrder = order;
+        return 0;
+    }
+
+    /**
+     * Toggles flat shading of an object's material. The material is recompiled and vertex
+     * normals are computed if the geometry has none.
+     * This is a no-op for materials which ignore normals (e.g. MeshBasicMaterial).
+     * @param id - The unique ID of the object.
+     * @param enabled - 1 to shade each face with a single normal, 0 for smooth shading.
+     * @returns 0 if the flag was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setFlatShading(id: number, enabled: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+        if (!('flatShading' in material)) {
+            return 0;
+        }
+
+        const geometry = (__OBJECTS.get(id) as THREE.Mesh).geometry;
+        if (geometry instanceof THREE.BufferGeometry && !geometry.hasAttribute('normal')) {
+            geometry.computeVertexNormals();
+        }
+        material.flatShading = enabled !== 0;
+        material.needsUpdate = true;
+        return 0;
+    }
+
+    /**
+     * Sets the color attribute of an object's geometry from sRGB float triplets.
+     * @param ptr - Pointer to the colors in WASM memory, as [r, g, b, ...] between 0 and 1.
+     * @param len - Number of floats, which must be 3 times the vertex count.
+     * @returns 0 if the colors were set successfully, or a negative ErrorCode otherwise.
+     */
+    function setVertexColors(id: number, ptr: number, len: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object || !__memory) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        const geometry = 'geometry' in object ? object.geometry : undefined;
+        if (!(geometry instanceof THREE.BufferGeometry) || len !== geometry.getAttribute('position').count * 3) {
+            console.error(`Object with ID ${id} has no geometry with ${len / 3} vertices.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        // copy, as the WASM memory may be reused or grow
+        const colors = readF32s(ptr, len).slice();
+        const color = new THREE.Color();
+        for (let i = 0; i < colors.length; i += 3) {
+            color.setRGB(colors[i], colors[i + 1], colors[i + 2], THREE.SRGBColorSpace);
+            color.toArray(colors, i);
+        }
+        geometry.setAttribute('color', new THREE.Float32BufferAttribute(colors, 3));
+        return 0;
+    }
+
+    /**
+     * Toggles the vertex colors of an object's material. The material is recompiled.
+     * @param enabled - 1 to multiply the material color with the vertex colors, 0 to ignore them.
+     * @returns 0 if the flag was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setVertexColorsEnabled(id: number, enabled: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        material.vertexColors = enabled !== 0;
+        material.needsUpdate = true;
+        return 0;
+    }
+
+    /**
+     * Sets the alpha test of an object's material: fragments with a lower alpha are discarded.
+     * @param id - The unique ID of the object.
+     * @param threshold - The alpha threshold between 0 and 1, where 0 disables the test.
+     * @returns 0 if the threshold was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setAlphaTest(id: number, threshold: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+        if (!(threshold >= 0 && threshold <= 1)) {
+            console.error(`Invalid alpha test threshold ${threshold}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        // three.js recompiles the shader when the test is turned on or off
+        material.alphaTest = threshold;
+        return 0;
+    }
+
+    /**
+     * Initializes an empty THREE.Group which can be used as a parent for other objects.
+     * @returns The ID of the created group.
+     */
+    function createGroup(): number {
+        const group: THREE.Object3D = new THREE.Group();
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, group);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * Initializes a THREE.LOD, which shows one of its levels depending on the distance to the camera.
+     * @returns The ID of the created LOD.
+     */
+    function createLOD(): number {
+        const lod: THREE.Object3D = new THREE.LOD();
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, lod);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * Adds an object as a level of a LOD. The object becomes a child of the LOD.
+     * @param lodId - The unique ID of the LOD.
+     * @param objectId - The unique ID of the object shown at this level.
+     * @param distance - Distance to the camera from which the level is shown.
+     * @returns 0 if the level was added, ErrorCode.UnknownObject if either object was not found, or ErrorCode.InvalidArgument if the first object is not a LOD.
+     */
+    function addLODLevel(lodId: number, objectId: number, distance: number): number {
+        const lod = __OBJECTS.get(lodId);
+        const object = __OBJECTS.get(objectId);
+        if (!lod || !object) {
+            console.error(`Object with ID ${!lod ? lodId : objectId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(lod instanceof THREE.LOD) || lod === object) {
+            console.error(`Object with ID ${lodId} is not a LOD.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        lod.addLevel(object, distance);
+        return 0;
+    }
+
+    /**
+     * Adds an object as a child of another object. The child's transform becomes relative to the parent.
+     * @param parentId - The unique ID of the parent object.
+     * @param childId - The unique ID of the child object.
+     * @returns 0 if the child was added successfully, or ErrorCode.UnknownObject if either object was not found.
+     */
+    function addChild(parentId: number, childId: number): number {
+        const parent = __OBJECTS.get(parentId);
+        const child = __OBJECTS.get(childId);
+        if (!parent || !child) {
+            console.error(`Object with ID ${!parent ? parentId : childId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        if (parent === child) {
+            console.error(`Object with ID ${parentId} can't be a child of itself.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        parent.add(child);
+        return 0;
+    }
+
+    /**
+     * Removes a child from its parent object.
+     * @param parentId - The unique ID of the parent object.
+     * @param childId - The unique ID of the child object.
+     * @returns 0 if the child was removed successfully, or ErrorCode.UnknownObject if either object was not found.
+     */
+    function removeChild(parentId: number, childId: number): number {
+        const parent = __OBJECTS.get(parentId);
+        const child = __OBJECTS.get(childId);
+        if (!parent || !child) {
+            console.error(`Object with ID ${!parent ? parentId : childId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        parent.remove(child);
+        return 0;
+    }
+
+    /**
+     * Assigns a texture to one of the map slots of an object's material.
+     * If the texture is not found the current map is left unchanged.
+     */
+    function setMaterialTexture(id: number, textureId: number, slot: 'map' | 'normalMap' | 'roughnessMap' | 'matcap'): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+
+        const texture = __TEXTURES.get(textureId);
+        if (!texture) {
+            console.error(`Texture ${textureId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        if (!(slot in material)) {
+            console.error(`Material of object ${id} has no ${slot}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        // @ts-ignore
+        material[slot] = texture;
+        material.needsUpdate = true;
+        return 0;
+    }
+
+    /**
+     * Sets the color map of an object's material.
+     * @param id - The unique ID of the object.
+     * @param textureId - The ID of the texture.
+     * @returns 0 if the map was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMaterialMap(id: number, textureId: number): number {
+        return setMaterialTexture(id, textureId, 'map');
+    }
+
+    /**
+     * Sets the normal map of an object's material.
+     * @returns 0 if the map was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMaterialNormalMap(id: number, textureId: number): number {
+        return setMaterialTexture(id, textureId, 'normalMap');
+    }
+
+    /**
+     * Sets the roughness map of an object's material (MeshStandardMaterial only).
+     * @returns 0 if the map was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMaterialRoughnessMap(id: number, textureId: number): number {
+        return setMaterialTexture(id, textureId, 'roughnessMap');
+    }
+
+    /**
+     * Sets the matcap texture of an object's material (MeshMatcapMaterial only).
+     * @returns 0 if the texture was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setMatcapTexture(id: number, textureId: number): number {
+        return setMaterialTexture(id, textureId, 'matcap');
+    }
+
+    /**
+     * Starts loading a texture and returns its ID right away.
+     * Objects using the texture render blank until it is loaded (see `textureIsReady`).
+     * @param urlPtr - Pointer to the UTF-8 encoded URL in WASM memory.
+     * @param urlLen - Length of the URL in bytes.
+     * @returns The ID of the texture, or a negative ErrorCode if an error occurred.
+     */
+    function loadTexture(urlPtr: number, urlLen: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const path = readString(urlPtr, urlLen);
+
+        while (__TEXTURES.has(__nextTextureId)) {
+            __nextTextureId++;
+        }
+        const id = __nextTextureId;
+
+        const texture = textureLoader.load(
+            path,
+            (texture) => {
+                texture.userData.ready = true;
+                pushEvent(EventKind.TextureLoaded, id);
+            },
+            undefined,
+            (error) => console.error(`Error loading texture ${path}: ${error}`),
+        );
+        texture.userData.ready = false;
+
+        __TEXTURES.set(id, texture);
+        return id;
+    }
+
+    /**
+     * Frees a texture (or render target) on the GPU and forgets its ID. Materials and the scene
+     * background or environment which still use it have it removed, so they render without it.
+     * Sprites own a clone of their texture and are not affected.
+     * @param id - The ID of the texture.
+     * @returns 0 if the texture was disposed, or ErrorCode.UnknownObject if it was not found.
+     */
+    function disposeTexture(id: number): number {
+        const texture = __TEXTURES.get(id);
+        if (!texture) {
+            console.error(`Texture ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        for (const object of __OBJECTS.values()) {
+            object.traverse((child) => {
+                if (!('material' in child) || !(child.material instanceof THREE.Material)) {
+                    return;
+                }
+                const material = child.material as THREE.Material & Record<string, unknown>;
+                for (const key of ['map', 'normalMap', 'roughnessMap', 'matcap']) {
+                    if (material[key] === texture) {
+                        material[key] = null;
+                        material.needsUpdate = true;
+                    }
+                }
+            });
+        }
+        if (__scene?.background === texture) {
+            __scene.background = null;
+        }
+        if (__scene?.environment === texture) {
+            __scene.environment = null;
+        }
+
+        const target = __RENDER_TARGETS.get(id);
+        if (target) {
+            target.dispose();
+            __RENDER_TARGETS.delete(id);
+        } else {
+            texture.dispose();
+        }
+        __TEXTURES.delete(id);
+        return 0;
+    }
+
+    /**
+     * Sets the anisotropic filtering level of a texture, clamped to the maximum supported by the device.
+     * @param id - The ID of the texture.
+     * @param level - The filtering level. 1 disables anisotropic filtering.
+     * @returns 0 if the level was set, ErrorCode.UnknownObject if the texture was not found, or ErrorCode.NotInitialized if the renderer is not initialized.
+     */
+    function setTextureAnisotropy(id: number, level: number): number {
+        const texture = __TEXTURES.get(id);
+        if (!texture) {
+            console.error(`Texture ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!__renderer) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        texture.anisotropy = Math.max(1, Math.min(level, __renderer.capabilities.getMaxAnisotropy()));
+        texture.needsUpdate = true;
+        return 0;
+    }
+
+    /**
+     * @returns The highest anisotropic filtering level supported by the device, or 1 if the renderer is not initialized.
+     */
+    function getMaxAnisotropy(): number {
+        return __renderer ? __renderer.capabilities.getMaxAnisotropy() : 1;
+    }
+
+    /**
+     * Sets how a texture is wrapped outside the 0..1 texture coordinates.
+     * @param id - The ID of the texture.
+     * @param wrapS - Horizontal wrapping, one of THREE.RepeatWrapping, THREE.ClampToEdgeWrapping or THREE.MirroredRepeatWrapping.
+     * @param wrapT - Vertical wrapping, same constants as wrapS.
+     * @returns 0 if the wrapping was set, ErrorCode.UnknownObject if the texture was not found, or ErrorCode.InvalidArgument if a mode is unknown.
+     */
+    function setTextureWrap(id: number, wrapS: number, wrapT: number): number {
+        const texture = __TEXTURES.get(id);
+        if (!texture) {
+            console.error(`Texture ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        const modes: number[] = [THREE.RepeatWrapping, THREE.ClampToEdgeWrapping, THREE.MirroredRepeatWrapping];
+        if (!modes.includes(wrapS) || !modes.includes(wrapT)) {
+            console.error(`Unknown wrapping (${wrapS}, ${wrapT}).`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        texture.wrapS = wrapS as THREE.Wrapping;
+        texture.wrapT = wrapT as THREE.Wrapping;
+        texture.needsUpdate = true;
+        return 0;
+    }
+
+    /**
+     * Sets how many times a texture is repeated across the surface. Only axes with repeat wrapping actually tile.
+     * @param id - The ID of the texture.
+     * @returns 0 if the repeat was set, or ErrorCode.UnknownObject if the texture was not found.
+     */
+    function setTextureRepeat(id: number, u: number, v: number): number {
+        const texture = __TEXTURES.get(id);
+        if (!texture) {
+            console.error(`Texture ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        texture.repeat.set(u, v);
+        return 0;
+    }
+
+    /**
+     * @returns 1 if the texture with the given ID finished loading, 0 otherwise.
+     */
+    function textureIsReady(id: number): number {
+        const texture = __TEXTURES.get(id);
+        return texture && texture.userData.ready !== false ? 1 : 0;
+    }
+
+    /**
+     * Enables linear fog in the scene.
+     * @param color - The color of the fog. Should usually match the background color.
+     * @param near - Distance at which the fog starts.
+     * @param far - Distance at which the fog is fully opaque.
+     * @returns 0 if the fog was set successfully, or ErrorCode.NotInitialized if the scene is not initialized.
+     */
+    function setFog(color: number, near: number, far: number): number {
+        if (!__scene) {
+            console.error("Scene is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        __scene.fog = new THREE.Fog(color, near, far);
+        return 0;
+    }
+
+    /**
+     * Enables exponential squared fog in the scene.
+     * @param color - The color of the fog. Should usually match the background color.
+     * @param density - How fast the fog grows dense.
+     * @returns 0 if the fog was set successfully, or ErrorCode.NotInitialized if the scene is not initialized.
+     */
+    function setFogExp2(color: number, density: number): number {
+        if (!__scene) {
+            console.error("Scene is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        __scene.fog = new THREE.FogExp2(color, density);
+        return 0;
+    }
+
+    /**
+     * Disables fog in the scene.
+     * @returns 0 if the fog was cleared successfully, or ErrorCode.NotInitialized if the scene is not initialized.
+     */
+    function clearFog(): number {
+        if (!__scene) {
+            console.error("Scene is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        __scene.fog = null;
+        return 0;
+    }
+
+    /**
+     * Casts a ray from the camera through the given point.
+     * @param ndcX - x in normalized device coordinates (-1 is the left edge, 1 the right one).
+     * @param ndcY - y in normalized device coordinates (-1 is the bottom edge, 1 the top one).
+     * @returns The ID of the closest object hit, or -1 if nothing was hit.
+     */
+    function raycastFromCamera(ndcX: number, ndcY: number): number {
+        __lastRaycastPoints.clear();
+        if (!__camera || !__scene) {
+            console.error("Scene or camera is not initialized.");
+            return -1;
+        }
+
+        raycaster.setFromCamera(new THREE.Vector2(ndcX, ndcY), __camera);
+        const intersections = raycaster.intersectObjects(__scene.children, true);
+
+        let closest = -1;
+        for (const intersection of intersections) {
+            const id = findObjectId(intersection.object);
+            if (id < 0 || __lastRaycastPoints.has(id)) {
+                continue;
+            }
+            if (closest < 0) {
+                closest = id;
+            }
+            __lastRaycastPoints.set(id, intersection.point.clone());
+        }
+
+        return closest;
+    }
+
+    /**
+     * Writes the point where the last raycast hit the given object into WASM memory.
+     * @param id - The unique ID of the object.
+     * @param outPtr - Pointer to a buffer of 3 floats.
+     * @returns 0 if the point was written, or -1 if the last raycast did not hit the object.
+     */
+    function raycastPoint(id: number, outPtr: number): number {
+        const point = __lastRaycastPoints.get(id);
+        if (!point || !__memory) {
+            return -1;
+        }
+
+        writeF32s(outPtr, point.toArray());
+        return 0;
+    }
+
+    /**
+     * Projects a world-space point with the active camera and writes its position in pixels,
+     * relative to the top-left corner of the canvas, into WASM memory as two floats.
+     * @param outPtr - Pointer to a buffer of 2 floats.
+     * @returns 0 if the position was written, ErrorCode.InvalidArgument if the point is behind the camera, or ErrorCode.NotInitialized if the context or WASM memory is not initialized.
+     */
+    function worldToScreen(x: number, y: number, z: number, outPtr: number): number {
+        if (!__camera || !__target || !__memory) {
+            console.error("Camera or WASM memory is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const ndc = new THREE.Vector3(x, y, z).project(__camera);
+        if (ndc.z > 1) {
+            return ErrorCode.InvalidArgument;
+        }
+        writeF32s(outPtr, [
+            (ndc.x + 1) / 2 * __target.clientWidth,
+            (1 - ndc.y) / 2 * __target.clientHeight,
+        ]);
+        return 0;
+    }
+
+    /**
+     * Writes the world-space point at the given distance from the active camera, along the ray
+     * through a pixel of the canvas, into WASM memory as three floats.
+     * @param sx - The x coordinate in pixels, from the left edge of the canvas.
+     * @param sy - The y coordinate in pixels, from the top edge of the canvas.
+     * @param depth - The distance from the camera in world units.
+     * @param outPtr - Pointer to a buffer of 3 floats.
+     * @returns 0 if the point was written, or ErrorCode.NotInitialized if the context or WASM memory is not initialized.
+     */
+    function screenToWorld(sx: number, sy: number, depth: number, outPtr: number): number {
+        if (!__camera || !__target || !__memory) {
+            console.error("Camera or WASM memory is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const ndc = new THREE.Vector2(
+            sx / __target.clientWidth * 2 - 1,
+            1 - sy / __target.clientHeight * 2,
+        );
+        raycaster.setFromCamera(ndc, __camera);
+        writeF32s(outPtr, raycaster.ray.at(depth, new THREE.Vector3()).toArray());
+        return 0;
+    }
+
+    /**
+     * Turns on shadow maps in the renderer.
+     * @returns 0 if shadows were enabled, or ErrorCode.NotInitialized if the renderer is not initialized.
+     */
+    function enableShadows(): number {
+        if (!__renderer) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        __renderer.shadowMap.enabled = true;
+        return 0;
+    }
+
+    /**
+     * Sets whether an object casts shadows.
+     * @returns 0 if the flag was set successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
+     */
+    function setCastShadow(id: number, enabled: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        object.castShadow = enabled !== 0;
+        return 0;
+    }
+
+    /**
+     * Sets whether an object receives shadows.
+     * @returns 0 if the flag was set successfully, or ErrorCode.UnknownObject if the object with the given ID was not found.
+     */
+    function setReceiveShadow(id: number, enabled: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        object.receiveShadow = enabled !== 0;
+        return 0;
+    }
+
+    /**
+     * Sets whether a light casts shadows. Only directional, spot and point lights support shadows.
+     * @returns 0 if the flag was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setLightCastShadow(id: number, enabled: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(object instanceof THREE.DirectionalLight || object instanceof THREE.SpotLight || object instanceof THREE.PointLight)) {
+            console.error(`Object with ID ${id} is not a light which can cast shadows.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        object.castShadow = enabled !== 0;
+        return 0;
+    }
+
+    /**
+     * Initializes a THREE.InstancedMesh object. It is not added to scene by default.
+     * All instances start with an identity transform.
+     * @param geometry - The type of geometry to use for the object, specified by GeometryClass enum.
+     * @param material - The type of material to use for the object, specified by MaterialClass enum.
+     * @param count - The number of instances. Can't be changed later.
+     * @returns The ID of the created object, or a negative ErrorCode if an error occurred.
+     */
+    function createInstancedMesh(geometry: GeometryClass, material: MaterialClass, count: number): number {
+        // @ts-ignore
+        let geometryClassInstance = THREE[GeometryClass[geometry]];
+        // @ts-ignore
+        let materialClassInstance = THREE[MaterialClass[material]];
+
+        if (count <= 0) {
+            console.error(`Instance count must be positive, got ${count}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        try {
+            const geometry: THREE.BufferGeometry = new geometryClassInstance();
+            const material: THREE.Material = new materialClassInstance({ color: 0xffffff });
+            const mesh: THREE.Object3D = new THREE.InstancedMesh(geometry, material, count);
+
+            const id = __nextObjId;
+            __OBJECTS.set(__nextObjId, mesh);
+            __nextObjId++;
+            return id;
+        } catch (error) {
+            console.error(`Error creating object: ${error}`);
+            return ErrorCode.JsException;
+        }
+    }
+
+    const __instanceMatrix = new THREE.Matrix4();
+    const __instancePosition = new THREE.Vector3();
+    const __instanceRotation = new THREE.Quaternion();
+    const __instanceScale = new THREE.Vector3();
+    const __instanceEuler = new THREE.Euler();
+
+    /**
+     * Sets the transform of a single instance. Takes effect after `updateInstances`.
+     * @param id - The unique ID of the instanced mesh.
+     * @param index - The index of the instance, between 0 and count - 1.
+     * @returns 0 if the transform was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setInstanceTransform(
+        id: number,
+        index: number,
+        px: number, py: number, pz: number,
+        rx: number, ry: number, rz: number,
+        sx: number, sy: number, sz: number,
+    ): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(object instanceof THREE.InstancedMesh)) {
+            console.error(`Object with ID ${id} is not an instanced mesh.`);
+            return ErrorCode.InvalidArgument;
+        }
+        if (index < 0 || index >= object.count) {
+            console.error(`Instance ${index} is out of range for object ${id}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        __instancePosition.set(px, py, pz);
+        __instanceRotation.setFromEuler(__instanceEuler.set(rx, ry, rz));
+        __instanceScale.set(sx, sy, sz);
+        __instanceMatrix.compose(__instancePosition, __instanceRotation, __instanceScale);
+        object.setMatrixAt(index, __instanceMatrix);
+        return 0;
+    }
+
+    /**
+     * Flushes the instance transforms of an instanced mesh to the GPU.
+     * @param id - The unique ID of the instanced mesh.
+     * @returns 0 if the instances were updated successfully, or a negative ErrorCode otherwise.
+     */
+    function updateInstances(id: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(object instanceof THREE.InstancedMesh)) {
+            console.error(`Object with ID ${id} is not an instanced mesh.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        object.instanceMatrix.needsUpdate = true;
+        object.computeBoundingSphere();
+        return 0;
+    }
+
+    /**
+     * Starts loading a sound and returns its ID right away.
+     * Playing a sound which is still loading is dropped.
+     * @param urlPtr - Pointer to the UTF-8 encoded URL in WASM memory.
+     * @param urlLen - Length of the URL in bytes.
+     * @returns The ID of the sound, or a negative ErrorCode if an error occurred.
+     */
+    function loadSound(urlPtr: number, urlLen: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const path = readString(urlPtr, urlLen);
+        const id = __nextSoundId++;
+        const sound: { buffer?: AudioBuffer } = {};
+        __SOUNDS.set(id, sound);
+
+        audioLoader.load(
+            path,
+            (buffer) => { sound.buffer = buffer; },
+            undefined,
+            (error) => console.error(`Error loading sound ${path}: ${error}`),
+        );
+        return id;
+    }
+
+    /** Pushes a SoundFinished event whenever the audio plays to its end (stopping it doesn't count). */
+    function reportFinished<T extends THREE.Audio<AudioNode>>(audio: T, id: number): T {
+        const onEnded = audio.onEnded.bind(audio);
+        audio.onEnded = () => {
+            onEnded();
+            pushEvent(EventKind.SoundFinished, id);
+        };
+        return audio;
+    }
+
+    /**
+     * Plays a sound.
+     * @param id - The ID of the sound.
+     * @param volume - The volume, 1 is the original volume.
+     * @param loop - 1 to loop the sound, 0 to play it once.
+     * @returns 0 if the sound is playing (or still loading), or ErrorCode.UnknownObject if the sound was not found.
+     */
+    function playSound(id: number, volume: number, loop: number): number {
+        const sound = __SOUNDS.get(id);
+        if (!sound) {
+            console.error(`Sound with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!sound.buffer) {
+            return 0;
+        }
+
+        sound.audio ??= reportFinished(new THREE.Audio(getAudioListener()).setBuffer(sound.buffer), id);
+        if (sound.audio.isPlaying) {
+            sound.audio.stop();
+        }
+        sound.audio.setVolume(volume).setLoop(loop !== 0).play();
+        return 0;
+    }
+
+    /**
+     * Plays a sound from a point in the scene.
+     * @param id - The ID of the sound.
+     * @returns 0 if the sound is playing (or still loading), or a negative ErrorCode otherwise.
+     */
+    function playSoundAt(id: number, x: number, y: number, z: number, volume: number, loop: number): number {
+        const sound = __SOUNDS.get(id);
+        if (!sound) {
+            console.error(`Sound with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!__scene) {
+            console.error("Scene is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        if (!sound.buffer) {
+            return 0;
+        }
+
+        if (!sound.positional) {
+            sound.positional = reportFinished(new THREE.PositionalAudio(getAudioListener()).setBuffer(sound.buffer), id);
+            __scene.add(sound.positional);
+        }
+        if (sound.positional.isPlaying) {
+            sound.positional.stop();
+        }
+        sound.positional.position.set(x, y, z);
+        sound.positional.setVolume(volume).setLoop(loop !== 0).play();
+        return 0;
+    }
+
+    /**
+     * Stops a sound.
+     * @param id - The ID of the sound.
+     * @returns 0 if the sound was stopped, or ErrorCode.UnknownObject if the sound was not found.
+     */
+    function stopSound(id: number): number {
+        const sound = __SOUNDS.get(id);
+        if (!sound) {
+            console.error(`Sound with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        if (sound.audio?.isPlaying) {
+            sound.audio.stop();
+        }
+        if (sound.positional?.isPlaying) {
+            sound.positional.stop();
+        }
+        return 0;
+    }
+
+    /**
+     * Initializes a THREE.Points object. It is not added to scene by default.
+     * @param ptr - Pointer to the positions in WASM memory, as interleaved x, y, z floats.
+     * @param len - Number of floats in the buffer (3 per point).
+     * @param material - The type of material to use, must be MaterialClass.PointsMaterial.
+     * @returns The ID of the created object, or a negative ErrorCode if an error occurred.
+     */
+    function createPoints(ptr: number, len: number, material: MaterialClass): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+        if (material !== MaterialClass.PointsMaterial || len % 3 !== 0) {
+            console.error(`Invalid point cloud (material ${MaterialClass[material]}, ${len} floats).`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        const geometry = new THREE.BufferGeometry();
+        // copy, as the WASM memory may be reused or grow
+        geometry.setAttribute('position', new THREE.Float32BufferAttribute(readF32s(ptr, len).slice(), 3));
+        const points: THREE.Object3D = new THREE.Points(geometry, new THREE.PointsMaterial({ color: 0xffffff }));
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, points);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * Sets the size of the points of a point cloud.
+     * @returns 0 if the size was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setPointsSize(id: number, size: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+        if (!(material instanceof THREE.PointsMaterial)) {
+            console.error(`Material of object ${id} is not a PointsMaterial.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.size = size;
+        return 0;
+    }
+
+    /**
+     * Sets whether the points of a point cloud shrink with their distance to the camera.
+     * With attenuation the point size is in world units, without it in pixels.
+     * @returns 0 if the flag was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setPointsSizeAttenuation(id: number, enabled: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+        if (!(material instanceof THREE.PointsMaterial)) {
+            console.error(`Material of object ${id} is not a PointsMaterial.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.sizeAttenuation = enabled !== 0;
+        material.needsUpdate = true;
+        return 0;
+    }
+
+    /**
+     * Initializes a THREE.Line object. It is not added to scene by default.
+     * @param ptr - Pointer to the points in WASM memory, as interleaved x, y, z floats.
+     * @param len - Number of floats in the buffer (3 per point).
+     * @param material - MaterialClass.LineBasicMaterial or MaterialClass.LineDashedMaterial.
+     * @returns The ID of the created object, or a negative ErrorCode if an error occurred.
+     */
+    function createLine(ptr: number, len: number, material: MaterialClass): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+        if ((material !== MaterialClass.LineBasicMaterial && material !== MaterialClass.LineDashedMaterial) || len % 3 !== 0) {
+            console.error(`Invalid line (material ${MaterialClass[material]}, ${len} floats).`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        const geometry = new THREE.BufferGeometry();
+        geometry.setAttribute('position', new THREE.Float32BufferAttribute(readF32s(ptr, len).slice(), 3));
+        const lineMaterial = material === MaterialClass.LineDashedMaterial
+            ? new THREE.LineDashedMaterial({ color: 0xffffff })
+            : new THREE.LineBasicMaterial({ color: 0xffffff });
+        const line = new THREE.Line(geometry, lineMaterial);
+        // dashed lines need the distances along the line
+        line.computeLineDistances();
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, line);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * Initializes a Line2 object, a line which can be wider than 1 pixel. It is not added to scene by default.
+     * @param ptr - Pointer to the points in WASM memory, as interleaved x, y, z floats.
+     * @param len - Number of floats in the buffer (3 per point).
+     * @param width - Width of the line in pixels.
+     * @param color - Color of the line.
+     * @returns The ID of the created object, or a negative ErrorCode if an error occurred.
+     */
+    function createLine2(ptr: number, len: number, width: number, color: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+        if (len % 3 !== 0) {
+            console.error(`Invalid line (${len} floats).`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        const geometry = new LineGeometry();
+        geometry.setPositions(readF32s(ptr, len).slice());
+        const material = new LineMaterial({ color, linewidth: width });
+        if (__renderer) {
+            __renderer.getSize(material.resolution);
+        }
+        __LINE_MATERIALS.add(material);
+        const line = new Line2(geometry, material);
+        line.computeLineDistances();
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, line);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * Sets the color of a line.
+     * @returns 0 if the color was set successfully, or a negative ErrorCode otherwise.
+     */
+    function setLineColor(id: number, color: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+        if (!(material instanceof THREE.LineBasicMaterial || material instanceof THREE.LineDashedMaterial || material instanceof LineMaterial)) {
+            console.error(`Material of object ${id} is not a line material.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.color.set(color);
+        return 0;
+    }
+
+    /**
+     * Sets the dash and gap sizes of a dashed line.
+     * @returns 0 if the parameters were set successfully, or a negative ErrorCode otherwise.
+     */
+    function setDashParams(id: number, dashSize: number, gapSize: number): number {
+        const material = getMaterial(id);
+        if (typeof material === 'number') {
+            return material;
+        }
+        if (!(material instanceof THREE.LineDashedMaterial)) {
+            console.error(`Material of object ${id} is not a LineDashedMaterial.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        material.dashSize = dashSize;
+        material.gapSize = gapSize;
+        return 0;
+    }
+
+    const __box = new THREE.Box3();
+    const __sphere = new THREE.Sphere();
+
+    /**
+     * Writes the world-space bounding box of an object into WASM memory.
+     * @param id - The unique ID of the object.
+     * @param outPtr - Pointer to a buffer of 6 floats: min x, y, z then max x, y, z.
+     * @returns 0 if the box was written, or a negative ErrorCode otherwise.
+     */
+    function getBoundingBox(id: number, outPtr: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        __box.setFromObject(object);
+        writeF32s(outPtr, [...__box.min.toArray(), ...__box.max.toArray()]);
+        return 0;
+    }
+
+    /**
+     * Writes a world-space bounding sphere of an object into WASM memory.
+     * @param id - The unique ID of the object.
+     * @param outPtr - Pointer to a buffer of 4 floats: center x, y, z then radius.
+     * @returns 0 if the sphere was written, or a negative ErrorCode otherwise.
+     */
+    function getBoundingSphere(id: number, outPtr: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        __box.setFromObject(object).getBoundingSphere(__sphere);
+        writeF32s(outPtr, [...__sphere.center.toArray(), __sphere.radius]);
+        return 0;
+    }
+
+    const __frustum = new THREE.Frustum();
+    const __point = new THREE.Vector3();
+
+    /**
+     * Tests whether an object's world-space bounding sphere intersects the view frustum of the active camera.
+     * Objects without geometry are tested by their world position.
+     * @param id - The unique ID of the object.
+     * @returns 1 if the object is in view, 0 if it is not, or a negative ErrorCode if the object was not found or the camera is not initialized.
+     */
+    function isInFrustum(id: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!__camera) {
+            console.error("Camera is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        __camera.updateMatrixWorld();
+        __matrix.multiplyMatrices(__camera.projectionMatrix, __camera.matrixWorldInverse);
+        __frustum.setFromProjectionMatrix(__matrix);
+
+        __box.setFromObject(object);
+        if (__box.isEmpty()) {
+            return __frustum.containsPoint(object.getWorldPosition(__point)) ? 1 : 0;
+        }
+        return __frustum.intersectsSphere(__box.getBoundingSphere(__sphere)) ? 1 : 0;
+    }
+
+    /**
+     * Starts loading a GLTF/GLB model. An empty root group is registered right away and the
+     * loaded scene is attached to it once it arrives, so transforms set on the returned ID
+     * apply to the model's root node.
+     * @param urlPtr - Pointer to the UTF-8 encoded URL in WASM memory.
+     * @param urlLen - Length of the URL in bytes.
+     * @returns The ID of the model's root object, or ErrorCode.NotInitialized if WASM memory is not bound.
+     */
+    function loadModel(urlPtr: number, urlLen: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const path = readString(urlPtr, urlLen);
+        const root = new THREE.Group();
+        root.userData.ready = false;
+        root.userData.animations = [];
+
+        gltfLoader.load(
+            path,
+            (gltf) => {
+                root.add(gltf.scene);
+                root.userData.animations = gltf.animations;
+                root.userData.ready = true;
+            },
+            undefined,
+            (error) => console.error(`Error loading model ${path}: ${error}`),
+        );
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, root);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * @returns 1 if the model with the given ID finished loading, 0 otherwise.
+     */
+    function modelIsReady(id: number): number {
+        const root = __OBJECTS.get(id);
+        return root && root.userData.ready === true ? 1 : 0;
+    }
+
+    /**
+     * @returns The number of animation clips embedded in the model (0 until it is loaded), or ErrorCode.UnknownObject if the model was not found.
+     */
+    function getModelAnimationCount(id: number): number {
+        const root = __OBJECTS.get(id);
+        if (!root) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        return (root.userData.animations as THREE.AnimationClip[] | undefined)?.length ?? 0;
+    }
+
+    /**
+     * Writes the UTF-8 encoded name of an animation clip into WASM memory.
+     * @param id - The unique ID of the model.
+     * @param index - Index of the clip, as listed by the loader.
+     * @param outPtr - Pointer to the output buffer.
+     * @param outCap - Size of the output buffer in bytes. Nothing is written if the name does not fit.
+     * @returns The length of the name in bytes, ErrorCode.UnknownObject if the model was not found, or ErrorCode.InvalidArgument if there is no clip with this index.
+     */
+    function getModelAnimationName(id: number, index: number, outPtr: number, outCap: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+        const root = __OBJECTS.get(id);
+        if (!root) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        const clip = (root.userData.animations as THREE.AnimationClip[] | undefined)?.[index];
+        if (!clip) {
+            console.error(`Model ${id} has no animation with index ${index}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        const bytes = utf8Encoder.encode(clip.name);
+        if (bytes.length <= outCap) {
+            new Uint8Array(__memory.buffer, outPtr, bytes.length).set(bytes);
+        }
+        return bytes.length;
+    }
+
+    /**
+     * Returns the animation clip of a loaded model.
+     */
+    function getAnimationClip(id: number, clipIndex: number): THREE.AnimationClip | ErrorCode {
+        const root = __OBJECTS.get(id);
+        if (!root) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        const clip = (root.userData.animations as THREE.AnimationClip[] | undefined)?.[clipIndex];
+        if (!clip) {
+            console.error(`Model ${id} has no animation with index ${clipIndex}.`);
+            return ErrorCode.InvalidArgument;
+        }
+        return clip;
+    }
+
+    /**
+     * Plays an animation clip of a loaded model, stopping the clip which was playing before.
+     * Calling this on a model which is not loaded yet does nothing.
+     * @param id - The unique ID of the model.
+     * @param clipIndex - Index of the clip, as listed by getModelAnimationName.
+     * @param loop - 1 to repeat the clip, 0 to play it once and hold the last frame.
+     * @returns 0 if the clip started (or the model is still loading), or a negative ErrorCode if the model or clip was not found.
+     */
+    function playAnimation(id: number, clipIndex: number, loop: number): number {
+        const root = __OBJECTS.get(id);
+        if (!root) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (root.userData.ready !== true) {
+            return 0;
+        }
+        const clip = getAnimationClip(id, clipIndex);
+        if (typeof clip === 'number') {
+            return clip;
+        }
+
+        let mixer = __MIXERS.get(id);
+        if (!mixer) {
+            mixer = new THREE.AnimationMixer(root);
+            __MIXERS.set(id, mixer);
+        }
+        mixer.stopAllAction();
+
+        const action = mixer.clipAction(clip);
+        action.setLoop(loop ? THREE.LoopRepeat : THREE.LoopOnce, Infinity);
+        action.clampWhenFinished = !loop;
+        action.reset().play();
+        return 0;
+    }
+
+    /**
+     * Stops all animations of a model.
+     * @returns 0 if the animations were stopped, or ErrorCode.UnknownObject if the model was not found.
+     */
+    function stopAnimation(id: number): number {
+        if (!__OBJECTS.has(id)) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        __MIXERS.get(id)?.stopAllAction();
+        return 0;
+    }
+
+    /**
+     * Jumps the animations of a model to the given time.
+     * @param id - The unique ID of the model.
+     * @param t - Time in seconds.
+     * @returns 0 if the time was set, or ErrorCode.UnknownObject if the model was not found.
+     */
+    function setAnimationTime(id: number, t: number): number {
+        if (!__OBJECTS.has(id)) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        __MIXERS.get(id)?.setTime(t);
+        return 0;
+    }
 
     /**
-     * Sets the camera to look at a specific point in the 3D scene.
-     * 
-     * @param x - The x-coordinate of the point to look at.
-     * @param y - The y-coordinate of the point to look at.
-     * @param z - The z-coordinate of the point to look at.
-     * @returns 0 if the camera was set to look at the point successfully, or -1 if the camera is not initialized.
+     * @returns The duration of the animation clip in seconds, or a negative ErrorCode if the model or clip was not found.
      */
-    function cameraLookAt(
-        x: number, y: number, z: number,
-    ) {
-        if (!__camera) {
-            return -1;
+    function getAnimationDuration(id: number, clipIndex: number): number {
+        const clip = getAnimationClip(id, clipIndex);
+        return typeof clip === 'number' ? clip : clip.duration;
+    }
+
+    /**
+     * Collects the meshes of a model which carry morph targets.
+     * @returns The meshes, or ErrorCode.UnknownObject if the model was not found.
+     */
+    function getMorphMeshes(id: number): THREE.Mesh[] | number {
+        const root = __OBJECTS.get(id);
+        if (!root) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
         }
+        const meshes: THREE.Mesh[] = [];
+        root.traverse((child) => {
+            if ((child as THREE.Mesh).isMesh && (child as THREE.Mesh).morphTargetInfluences) {
+                meshes.push(child as THREE.Mesh);
+            }
+        });
+        return meshes;
+    }
 
-        __camera.lookAt(x, y, z);
+    /**
+     * Sets the influence of a morph target on every mesh of the model which has it.
+     * @param index - Index of the morph target.
+     * @param weight - Influence of the target, between 0 and 1.
+     * @returns 0 if the weight was set, ErrorCode.UnknownObject if the model was not found, or ErrorCode.InvalidArgument if no mesh has a morph target with this index.
+     */
+    function setMorphWeight(id: number, index: number, weight: number): number {
+        const meshes = getMorphMeshes(id);
+        if (typeof meshes === 'number') {
+            return meshes;
+        }
+        let found = false;
+        for (const mesh of meshes) {
+            const influences = mesh.morphTargetInfluences!;
+            if (index < influences.length) {
+                influences[index] = weight;
+                found = true;
+            }
+        }
+        if (!found) {
+            console.error(`Model ${id} has no morph target with index ${index}.`);
+            return ErrorCode.InvalidArgument;
+        }
+        return 0;
+    }
+
+    /**
+     * @returns The number of morph targets of the model (0 until it is loaded), or ErrorCode.UnknownObject if the model was not found.
+     */
+    function getMorphTargetCount(id: number): number {
+        const meshes = getMorphMeshes(id);
+        if (typeof meshes === 'number') {
+            return meshes;
+        }
+        return meshes.reduce((max, mesh) => Math.max(max, mesh.morphTargetInfluences!.length), 0);
+    }
+
+    /**
+     * Writes the size of the element passed to `init` into WASM memory, as two i32s (width, height).
+     * @param outPtr - Pointer to the output buffer.
+     * @returns 0 if the size was written, or ErrorCode.NotInitialized if the context or WASM memory is not initialized.
+     */
+    function getCanvasSize(outPtr: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+        if (!__target) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
 
+        new Int32Array(__memory.buffer, outPtr, 2).set([__target.clientWidth, __target.clientHeight]);
         return 0;
     }
 
     /**
-     * Initializes the Three.js context with a renderer, scene, and camera.
-     * @param target - The HTML element to attach the renderer's canvas to.
+     * Encodes the canvas as a PNG file and writes it into WASM memory.
+     * Without preserveDrawingBuffer the canvas is only intact right after render, in the same frame.
+     * @param outPtr - Pointer to the output buffer.
+     * @param maxLen - Size of the output buffer in bytes. Nothing is written if the PNG does not fit.
+     * @returns The length of the PNG in bytes, or ErrorCode.NotInitialized if the renderer or WASM memory is not initialized.
      */
-    function init(
-        target: HTMLElement,
-    ) {
-        __camera = new THREE.PerspectiveCamera(75, target.clientWidth / target.clientHeight, 0.1, 1000);
-        __scene = new THREE.Scene();
-        __renderer = new THREE.WebGLRenderer({ antialias: true });
+    function captureFrame(outPtr: number, maxLen: number): number {
+        if (!__renderer || !__memory) {
+            console.error("Renderer or WASM memory is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
 
-        __renderer.setSize(target.clientWidth, target.clientHeight);
-        target.appendChild(__renderer.domElement);
+        const base64 = __renderer.domElement.toDataURL('image/png').split(',')[1] ?? '';
+        const binary = atob(base64);
+        if (binary.length <= maxLen) {
+            const out = new Uint8Array(__memory.buffer, outPtr, binary.length);
+            for (let i = 0; i < binary.length; i++) {
+                out[i] = binary.charCodeAt(i);
+            }
+        }
+        return binary.length;
     }
 
-    /** 
-     * Sets the background color of the scene.
+    /**
+     * Resizes the renderer's drawing buffer and canvas.
+     * @returns 0 if the renderer was resized, or ErrorCode.NotInitialized if the renderer is not initialized.
      */
-    function setBg(color: number) {
+    function setRendererSize(width: number, height: number): number {
+        if (!__renderer) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        __renderer.setSize(width, height);
+        __composer?.setSize(width, height);
+        for (const material of __LINE_MATERIALS) {
+            material.resolution.set(width, height);
+        }
+        return 0;
+    }
+
+    /**
+     * Sets the order in which the rotation angles of an object are applied.
+     * @param id - The unique ID of the object.
+     * @param order - The rotation order, specified by RotationOrder enum.
+     * @returns 0 if the order was set successfully, or a negative ErrorCode if the object was not found or the order is invalid.
+     */
+    function setRotationOrder(id: number, order: RotationOrder): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        const name = RotationOrder[order] as THREE.EulerOrder | undefined;
+        if (!name) {
+            console.error(`Unknown rotation order ${order}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        object.rotation.order = name;
+        return 0;
+    }
+
+    /**
+     * Makes an object face the active camera on every render. While enabled, the rotation
+     * of the object is overwritten each frame.
+     * @param id - The unique ID of the object.
+     * @param enabled - 1 to face the camera, 0 to keep the rotation which was set last.
+     * @returns 0 if the flag was set successfully, or ErrorCode.UnknownObject if the object was not found.
+     */
+    function setBillboard(id: number, enabled: number): number {
+        if (!__OBJECTS.has(id)) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        if (enabled) {
+            __BILLBOARDS.add(id);
+        } else {
+            __BILLBOARDS.delete(id);
+        }
+        return 0;
+    }
+
+    /**
+     * Rotates billboarded objects so that they face the active camera.
+     */
+    function updateBillboards() {
+        const parentRotation = new THREE.Quaternion();
+        for (const id of __BILLBOARDS) {
+            const object = __OBJECTS.get(id)!;
+            // the camera rotation is in world space, the object rotation relative to its parent
+            object.quaternion.copy(__camera.quaternion);
+            if (object.parent) {
+                object.parent.getWorldQuaternion(parentRotation);
+                object.quaternion.premultiply(parentRotation.invert());
+            }
+        }
+    }
+
+    /**
+     * Hides or shows an object without removing it from the scene graph.
+     * @param id - The unique ID of the object.
+     * @param visible - 1 to show the object, 0 to hide it (together with its children).
+     * @returns 0 if the visibility was set successfully, or ErrorCode.UnknownObject if the object was not found.
+     */
+    function setVisible(id: number, visible: number): number {
+        const object = __OBJECTS.get(id);
+        if (!object) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        object.visible = visible !== 0;
+        return 0;
+    }
+
+    /**
+     * Creates an additional perspective camera with the same defaults as the one created by `init`.
+     * @returns The ID of the created camera, or ErrorCode.NotInitialized if the context is not initialized.
+     */
+    function createCamera(): number {
+        if (!__target) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const camera = new THREE.PerspectiveCamera(75, __target.clientWidth / __target.clientHeight, 0.1, 1000);
+
+        const id = __nextCameraId;
+        __CAMERAS.set(__nextCameraId, camera);
+        __nextCameraId++;
+        return id;
+    }
+
+    /**
+     * Creates an off-screen render target. Its texture is registered like a loaded one,
+     * so it can be assigned with setMaterialMap.
+     * @param width - Width of the target in pixels.
+     * @param height - Height of the target in pixels.
+     * @returns The texture ID of the render target, or ErrorCode.InvalidArgument if the size is not positive.
+     */
+    function createRenderTarget(width: number, height: number): number {
+        if (width <= 0 || height <= 0) {
+            console.error(`Invalid render target size ${width}x${height}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        while (__TEXTURES.has(__nextTextureId)) {
+            __nextTextureId++;
+        }
+        const id = __nextTextureId;
+
+        const target = new THREE.WebGLRenderTarget(width, height);
+        __RENDER_TARGETS.set(id, target);
+        __TEXTURES.set(id, target.texture);
+        return id;
+    }
+
+    /**
+     * Renders the scene from the given camera into a render target.
+     * @param targetId - The texture ID returned by createRenderTarget.
+     * @param cameraId - The ID of the camera to render from.
+     * @returns 0 if the scene was rendered, or a negative ErrorCode if the target or camera was not found.
+     */
+    function renderToTarget(targetId: number, cameraId: number): number {
+        if (!__renderer || !__scene) {
+            console.error("Renderer or scene is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        const target = __RENDER_TARGETS.get(targetId);
+        if (!target) {
+            console.error(`Render target ${targetId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        const camera = __CAMERAS.get(cameraId);
+        if (!camera) {
+            console.error(`Camera ${cameraId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        __renderer.setRenderTarget(target);
+        __renderer.render(__scene, camera);
+        __renderer.setRenderTarget(null);
+        return 0;
+    }
+
+    /**
+     * Starts loading a cube map and stores it in the TEXTURES map.
+     * @param urlsPtr - Pointer to six UTF-8 encoded, newline-separated URLs in WASM memory, ordered px, nx, py, ny, pz, nz.
+     * @param urlsLen - Length of the URLs in bytes.
+     * @returns The ID of the cube texture, or a negative ErrorCode if WASM memory is not bound or there are not six URLs.
+     */
+    function loadCubeMap(urlsPtr: number, urlsLen: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+
+        const urls = readString(urlsPtr, urlsLen).split("\n");
+        if (urls.length !== 6) {
+            console.error(`A cube map needs 6 faces, got ${urls.length}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        while (__TEXTURES.has(__nextTextureId)) {
+            __nextTextureId++;
+        }
+        const id = __nextTextureId;
+
+        const texture = cubeTextureLoader.load(
+            urls,
+            (texture) => {
+                texture.userData.ready = true;
+                pushEvent(EventKind.TextureLoaded, id);
+            },
+            undefined,
+            (error) => console.error(`Error loading cube map ${urls[0]}: ${error}`),
+        );
+        texture.userData.ready = false;
+
+        __TEXTURES.set(id, texture);
+        return id;
+    }
+
+    /**
+     * Sets a texture as the scene background.
+     * @returns 0 if the background was set successfully, or a negative ErrorCode if the scene is not initialized or the texture was not found.
+     */
+    function setSceneBackground(textureId: number): number {
         if (!__scene) {
             console.error("Scene is not initialized.");
-            return -1;
+            return ErrorCode.NotInitialized;
         }
-        __scene.background = new THREE.Color(color);
+        const texture = __TEXTURES.get(textureId);
+        if (!texture) {
+            console.error(`Texture ${textureId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        __scene.background = texture;
         return 0;
     }
 
-    function render() {
+    /**
+     * Sets a texture as the environment map used by all physically based materials of the scene.
+     * @returns 0 if the environment was set successfully, or a negative ErrorCode if the scene is not initialized or the texture was not found.
+     */
+    function setSceneEnvironment(textureId: number): number {
+        if (!__scene) {
+            console.error("Scene is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        const texture = __TEXTURES.get(textureId);
+        if (!texture) {
+            console.error(`Texture ${textureId} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+
+        __scene.environment = texture;
+        return 0;
+    }
+
+    /**
+     * Initializes a THREE.GridHelper on the XZ plane. It is not added to scene by default.
+     * @param size - The length of the sides of the grid.
+     * @param divisions - The number of cells along each side.
+     * @param color - The color of the grid lines.
+     * @returns The ID of the created grid.
+     */
+    function createGrid(size: number, divisions: number, color: number): number {
+        const grid = new THREE.GridHelper(size, divisions, color, color);
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, grid);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * Initializes a THREE.AxesHelper showing the x, y and z axes in red, green and blue. It is not added to scene by default.
+     * @param size - The length of the axes.
+     * @returns The ID of the created helper.
+     */
+    function createAxesHelper(size: number): number {
+        const axes = new THREE.AxesHelper(size);
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, axes);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * Sets the device pixel ratio of the renderer.
+     * @returns 0 if the ratio was set successfully, or a negative ErrorCode if the renderer is not initialized or the ratio is not positive.
+     */
+    function setPixelRatio(ratio: number): number {
+        if (!__renderer) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        if (!(ratio > 0)) {
+            console.error(`Invalid pixel ratio ${ratio}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        __renderer.setPixelRatio(ratio);
+        __composer?.setPixelRatio(ratio);
+        return 0;
+    }
+
+    /**
+     * Turns antialiasing on or off. As WebGL only supports this when the context is created,
+     * the renderer is replaced by a new one with the same settings and its canvas takes the
+     * place of the old one.
+     * @returns 0 if the setting was applied, or ErrorCode.NotInitialized if the renderer is not initialized.
+     */
+    function setAntialias(enabled: number): number {
+        if (!__renderer || !__target) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        if (__antialias === (enabled !== 0)) {
+            return 0;
+        }
+        __antialias = enabled !== 0;
+
+        const old = __renderer;
+        const size = old.getSize(new THREE.Vector2());
+        __renderer = new THREE.WebGLRenderer({ antialias: __antialias });
+        __renderer.setPixelRatio(old.getPixelRatio());
+        __renderer.setSize(size.x, size.y);
+        __renderer.shadowMap.enabled = old.shadowMap.enabled;
+        __renderer.toneMapping = old.toneMapping;
+        __renderer.toneMappingExposure = old.toneMappingExposure;
+
+        old.domElement.replaceWith(__renderer.domElement);
+        old.dispose();
+        // the composer draws with the renderer it was created for
+        rebuildComposer();
+        return 0;
+    }
+
+    /**
+     * Sets the tone mapping of the renderer.
+     * @param mode - One of the three.js tone mapping constants (THREE.NoToneMapping, THREE.ACESFilmicToneMapping, ...).
+     * @param exposure - Exposure level of the tone mapping.
+     * @returns 0 if the tone mapping was set successfully, or a negative ErrorCode if the renderer is not initialized or the mode is unknown.
+     */
+    function setToneMapping(mode: number, exposure: number): number {
+        if (!__renderer) {
+            console.error("Renderer is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        const modes: number[] = [
+            THREE.NoToneMapping,
+            THREE.LinearToneMapping,
+            THREE.ReinhardToneMapping,
+            THREE.CineonToneMapping,
+            THREE.ACESFilmicToneMapping,
+            THREE.AgXToneMapping,
+            THREE.NeutralToneMapping,
+        ];
+        if (!modes.includes(mode)) {
+            console.error(`Unknown tone mapping ${mode}.`);
+            return ErrorCode.InvalidArgument;
+        }
+
+        __renderer.toneMapping = mode as THREE.ToneMapping;
+        __renderer.toneMappingExposure = exposure;
+        return 0;
+    }
+
+    /** The composer renders into half float targets, which not every device can draw to. */
+    function supportsPostProcessing(): boolean {
+        return __renderer.extensions.has('EXT_color_buffer_float')
+            || __renderer.extensions.has('EXT_color_buffer_half_float');
+    }
+
+    /** Recreates the post-processing composer from the current settings, or removes it if none are on. */
+    function rebuildComposer() {
+        __composer?.dispose();
+        __composer = undefined;
+        if (!__ssao && !__bloom) {
+            return;
+        }
+
+        const size = __renderer.getSize(new THREE.Vector2());
+        const composer = new EffectComposer(__renderer);
+        composer.addPass(new RenderPass(__scene, __camera));
+        if (__ssao) {
+            const ao = new GTAOPass(__scene, __camera, size.x, size.y);
+            ao.blendIntensity = __ssao.intensity;
+            ao.updateGtaoMaterial({ radius: __ssao.radius });
+            composer.addPass(ao);
+        }
+        if (__bloom) {
+            composer.addPass(new UnrealBloomPass(size, __bloom.strength, __bloom.radius, __bloom.threshold));
+        }
+        // applies tone mapping and color space conversion, which the renderer skips for render targets
+        composer.addPass(new OutputPass());
+        __composer = composer;
+    }
+
+    /**
+     * Turns screen-space ambient occlusion (a THREE GTAOPass) on or off. While it is on,
+     * render() draws through an EffectComposer instead of the plain renderer. If the device
+     * can't render to float textures, which the composer needs, a warning is logged and
+     * nothing changes.
+     * @param enabled - 1 to turn the effect on, 0 to turn it off.
+     * @param radius - The radius of the occlusion in world units.
+     * @param intensity - How strongly the occlusion darkens the picture, from 0 to 1.
+     * @returns 0 if the setting was applied (or skipped on an unsupported device), or a negative ErrorCode if the renderer is not initialized or the parameters are invalid.
+     */
+    function setSSAO(enabled: number, radius: number, intensity: number): number {
         if (!__renderer || !__scene || !__camera) {
             console.error("Renderer, scene, or camera is not initialized.");
-            return -1;
+            return ErrorCode.NotInitialized;
+        }
+        if (enabled && (!(radius > 0) || !(intensity >= 0 && intensity <= 1))) {
+            console.error(`Invalid SSAO radius ${radius} or intensity ${intensity}.`);
+            return ErrorCode.InvalidArgument;
+        }
+        if (enabled && !supportsPostProcessing()) {
+            console.warn("SSAO is not supported on this device.");
+            return 0;
+        }
+
+        __ssao = enabled ? { radius, intensity } : undefined;
+        rebuildComposer();
+        return 0;
+    }
+
+    /**
+     * Turns bloom (a THREE UnrealBloomPass) on or off. Like setSSAO, render() draws through an
+     * EffectComposer while it is on, and unsupported devices only log a warning.
+     * @param enabled - 1 to turn the effect on, 0 to turn it off.
+     * @param strength - How strongly bright pixels glow.
+     * @param radius - How far the glow spreads, from 0 to 1.
+     * @param threshold - The brightness above which pixels glow, from 0 to 1.
+     * @returns 0 if the setting was applied (or skipped on an unsupported device), or a negative ErrorCode if the renderer is not initialized or the parameters are invalid.
+     */
+    function setBloom(enabled: number, strength: number, radius: number, threshold: number): number {
+        if (!__renderer || !__scene || !__camera) {
+            console.error("Renderer, scene, or camera is not initialized.");
+            return ErrorCode.NotInitialized;
+        }
+        if (enabled && (!(strength >= 0) || !(radius >= 0 && radius <= 1) || !(threshold >= 0 && threshold <= 1))) {
+            console.error(`Invalid bloom strength ${strength}, radius ${radius} or threshold ${threshold}.`);
+            return ErrorCode.InvalidArgument;
+        }
+        if (enabled && !supportsPostProcessing()) {
+            console.warn("Bloom is not supported on this device.");
+            return 0;
+        }
+
+        __bloom = enabled ? { strength, radius, threshold } : undefined;
+        rebuildComposer();
+        return 0;
+    }
+
+    /** Height in pixels of the canvas text sprites are drawn on */
+    const TEXT_SPRITE_FONT_SIZE = 64;
+
+    /**
+     * Draws the text on the canvas of a text sprite and resizes the sprite to match.
+     */
+    function drawTextSprite(sprite: THREE.Sprite, text: string) {
+        const { canvas, color, size } = sprite.userData as { canvas: HTMLCanvasElement, color: number, size: number };
+        const context = canvas.getContext('2d')!;
+        const font = `${TEXT_SPRITE_FONT_SIZE}px sans-serif`;
+
+        context.font = font;
+        canvas.width = Math.max(1, Math.ceil(context.measureText(text).width));
+        canvas.height = TEXT_SPRITE_FONT_SIZE;
+
+        // resizing the canvas resets the context
+        context.font = font;
+        context.textBaseline = 'middle';
+        context.fillStyle = `#${color.toString(16).padStart(6, '0')}`;
+        context.fillText(text, 0, canvas.height / 2);
+
+        // the canvas size changes, so the texture has to be recreated
+        sprite.material.map?.dispose();
+        sprite.material.map = new THREE.CanvasTexture(canvas);
+        sprite.material.needsUpdate = true;
+        sprite.scale.set(size * canvas.width / canvas.height, size, 1);
+    }
+
+    /**
+     * Initializes a THREE.Sprite showing a text. It is not added to scene by default.
+     * @param textPtr - Pointer to the UTF-8 encoded text in WASM memory.
+     * @param textLen - Length of the text in bytes.
+     * @param size - Height of the text in world units.
+     * @param color - Color of the text.
+     * @returns The ID of the created sprite, or ErrorCode.NotInitialized if WASM memory is not bound.
+     */
+    function createTextSprite(textPtr: number, textLen: number, size: number, color: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
         }
 
-        __renderer.render(__scene, __camera);
+        const sprite = new THREE.Sprite(new THREE.SpriteMaterial({ transparent: true }));
+        sprite.userData = { canvas: document.createElement('canvas'), color, size };
+        drawTextSprite(sprite, readString(textPtr, textLen));
+
+        const id = __nextObjId;
+        __OBJECTS.set(__nextObjId, sprite);
+        __nextObjId++;
+        return id;
+    }
+
+    /**
+     * Changes the text of a sprite created with createTextSprite.
+     * @returns 0 if the text was updated, or a negative ErrorCode if the object was not found or is not a text sprite.
+     */
+    function updateTextSprite(id: number, textPtr: number, textLen: number): number {
+        if (!__memory) {
+            console.error("WASM memory is not bound.");
+            return ErrorCode.NotInitialized;
+        }
+        const sprite = __OBJECTS.get(id);
+        if (!sprite) {
+            console.error(`Object with ID ${id} not found.`);
+            return ErrorCode.UnknownObject;
+        }
+        if (!(sprite instanceof THREE.Sprite) || !sprite.userData.canvas) {
+            console.error(`Object ${id} is not a text sprite.`);
+            return ErrorCode.InvalidArgument;
+        }
 
+        drawTextSprite(sprite, readString(textPtr, textLen));
         return 0;
     }
 
     return {
         createObject,
+        createObjectParameterized,
         createSprite,
         addTexture,
         setPosition,
         setRotation,
+        setQuaternion,
+        setRotationOrder,
         setScale,
+        setTransform,
+        setMatrix,
         setBg,
         addObjectToScene,
         removeObjectFromScene,
+        disposeObject,
+        cloneObject,
+        cloneObjectDeep,
+        clearScene,
+        setVisible,
+        setBillboard,
         setSpriteAnimationOffset,
+        loadAtlas,
+        atlasIsReady,
+        getAtlasFrame,
+        setSpriteFrame,
         // camera
         setCameraPosition,
+        setCameraPositionFor,
         cameraLookAt,
+        cameraLookAtFor,
+        setObjectLayer,
+        setCameraLayers,
+        setActiveCamera,
+        setCameraFov,
+        setCameraAspect,
+        setCameraClip,
+        useOrthographicCamera,
+        usePerspectiveCamera,
         init,
         // input
         getKeysPressed,
+        getTypedText,
+        setImeCapture,
         getMouseMovement,
+        getScrollDelta,
+        pollEvent,
+        getMouseButtons,
+        getMousePosition,
+        getGamepadState,
+        getGamepadAxis,
         initInputListeners,
+        bindMemory,
+        bindInstance,
         // rendering
         render,
+        requestFrame,
+        pauseLoop,
+        resumeLoop,
+        isPaused,
+        getDeltaTime,
+        getElapsedTime,
+        getRenderStats,
+        // lights
+        createLight,
+        setLightParams,
+        setLightTarget,
+        // materials
+        setMaterialColor,
+        setMaterialOpacity,
+        setObjectOpacity,
+        unshareMaterial,
+        setMaterialPBR,
+        setEnvMapIntensity,
+        setMaterialEmissive,
+        setWireframe,
+        setMaterialSide,
+        setDepthTest,
+        setDepthWrite,
+        setRenderOrder,
+        setFlatShading,
+        setAlphaTest,
+        setVertexColors,
+        setVertexColorsEnabled,
+        // scene graph
+        createGroup,
+        createLOD,
+        addLODLevel,
+        addChild,
+        removeChild,
+        // textures
+        loadTexture,
+        textureIsReady,
+        disposeTexture,
+        setTextureAnisotropy,
+        getMaxAnisotropy,
+        setTextureWrap,
+        setTextureRepeat,
+        setMaterialMap,
+        setMaterialNormalMap,
+        setMaterialRoughnessMap,
+        setMatcapTexture,
+        // fog
+        setFog,
+        setFogExp2,
+        clearFog,
+        // raycasting
+        raycastFromCamera,
+        raycastPoint,
+        worldToScreen,
+        screenToWorld,
+        // shadows
+        enableShadows,
+        setCastShadow,
+        setReceiveShadow,
+        setLightCastShadow,
+        // instancing
+        createInstancedMesh,
+        setInstanceTransform,
+        updateInstances,
+        // audio
+        loadSound,
+        playSound,
+        playSoundAt,
+        stopSound,
+        // points
+        createPoints,
+        setPointsSize,
+        setPointsSizeAttenuation,
+        // lines
+        createLine,
+        createLine2,
+        setLineColor,
+        setDashParams,
+        // bounds
+        getBoundingBox,
+        getBoundingSphere,
+        isInFrustum,
+        // models
+        loadModel,
+        modelIsReady,
+        getModelAnimationCount,
+        getModelAnimationName,
+        // animations
+        playAnimation,
+        stopAnimation,
+        setAnimationTime,
+        getAnimationDuration,
+        setMorphWeight,
+        getMorphTargetCount,
+        // canvas
+        getCanvasSize,
+        captureFrame,
+        setRendererSize,
+        // render targets
+        createCamera,
+        createRenderTarget,
+        renderToTarget,
+        // environment
+        loadCubeMap,
+        setSceneBackground,
+        setSceneEnvironment,
+        // helpers
+        createGrid,
+        createAxesHelper,
+        // renderer
+        setPixelRatio,
+        setAntialias,
+        setToneMapping,
+        setSSAO,
+        setBloom,
+        // text
+        createTextSprite,
+        updateTextSprite,
         __OBJECTS,
         __TEXTURES,
         __LOADED_TEXTURES,
@@ -426,23 +3738,168 @@ export const createContext = () => {
         createWasmEnv() {
             const exports: any = {};
             exports.createObject = createObject;
+            exports.createObjectParameterized = createObjectParameterized;
             exports.createSprite = createSprite;
             exports.setPosition = setPosition;
             exports.setRotation = setRotation;
+            exports.setQuaternion = setQuaternion;
             exports.setScale = setScale;
+            exports.setTransform = setTransform;
+            exports.setMatrix = setMatrix;
             exports.setBg = setBg;
             exports.addObjectToScene = addObjectToScene;
             exports.removeObjectFromScene = removeObjectFromScene;
+            exports.disposeObject = disposeObject;
+            exports.cloneObject = cloneObject;
+            exports.cloneObjectDeep = cloneObjectDeep;
+            exports.clearScene = clearScene;
             exports.setSpriteAnimationOffset = setSpriteAnimationOffset;
+            exports.loadAtlas = loadAtlas;
+            exports.atlasIsReady = atlasIsReady;
+            exports.getAtlasFrame = getAtlasFrame;
+            exports.setSpriteFrame = setSpriteFrame;
             exports.setCameraPosition = setCameraPosition;
             exports.cameraLookAt = cameraLookAt;
+            exports.setCameraFov = setCameraFov;
+            exports.setCameraAspect = setCameraAspect;
+            exports.setCameraClip = setCameraClip;
+            exports.useOrthographicCamera = useOrthographicCamera;
+            exports.usePerspectiveCamera = usePerspectiveCamera;
             exports.getKeysPressed = getKeysPressed;
+            exports.getTypedText = getTypedText;
+            exports.setImeCapture = setImeCapture;
             exports.getMouseMovement = getMouseMovement;
+            exports.getScrollDelta = getScrollDelta;
+            exports.pollEvent = pollEvent;
+            exports.getMouseButtons = getMouseButtons;
+            exports.getMousePosition = getMousePosition;
+            exports.getGamepadState = getGamepadState;
+            exports.getGamepadAxis = getGamepadAxis;
+            exports.render = render;
+            exports.requestFrame = requestFrame;
+            exports.pauseLoop = pauseLoop;
+            exports.resumeLoop = resumeLoop;
+            exports.isPaused = isPaused;
+            exports.getDeltaTime = getDeltaTime;
+            exports.getElapsedTime = getElapsedTime;
+            exports.getRenderStats = getRenderStats;
+            exports.createLight = createLight;
+            exports.setLightParams = setLightParams;
+            exports.setLightTarget = setLightTarget;
+            exports.setMaterialColor = setMaterialColor;
+            exports.setMaterialOpacity = setMaterialOpacity;
+            exports.setObjectOpacity = setObjectOpacity;
+            exports.unshareMaterial = unshareMaterial;
+            exports.setMaterialPBR = setMaterialPBR;
+            exports.setEnvMapIntensity = setEnvMapIntensity;
+            exports.setMaterialEmissive = setMaterialEmissive;
+            exports.setWireframe = setWireframe;
+            exports.setMaterialSide = setMaterialSide;
+            exports.setDepthTest = setDepthTest;
+            exports.setDepthWrite = setDepthWrite;
+            exports.setRenderOrder = setRenderOrder;
+            exports.setFlatShading = setFlatShading;
+            exports.setAlphaTest = setAlphaTest;
+            exports.setVertexColors = setVertexColors;
+            exports.setVertexColorsEnabled = setVertexColorsEnabled;
+            exports.createGroup = createGroup;
+            exports.createLOD = createLOD;
+            exports.addLODLevel = addLODLevel;
+            exports.addChild = addChild;
+            exports.removeChild = removeChild;
+            exports.loadTexture = loadTexture;
+            exports.textureIsReady = textureIsReady;
+            exports.disposeTexture = disposeTexture;
+            exports.setTextureAnisotropy = setTextureAnisotropy;
+            exports.getMaxAnisotropy = getMaxAnisotropy;
+            exports.setTextureWrap = setTextureWrap;
+            exports.setTextureRepeat = setTextureRepeat;
+            exports.setMaterialMap = setMaterialMap;
+            exports.setMaterialNormalMap = setMaterialNormalMap;
+            exports.setMaterialRoughnessMap = setMaterialRoughnessMap;
+            exports.setMatcapTexture = setMatcapTexture;
+            exports.setFog = setFog;
+            exports.setFogExp2 = setFogExp2;
+            exports.clearFog = clearFog;
+            exports.raycastFromCamera = raycastFromCamera;
+            exports.raycastPoint = raycastPoint;
+            exports.worldToScreen = worldToScreen;
+            exports.screenToWorld = screenToWorld;
+            exports.enableShadows = enableShadows;
+            exports.setCastShadow = setCastShadow;
+            exports.setReceiveShadow = setReceiveShadow;
+            exports.setLightCastShadow = setLightCastShadow;
+            exports.createInstancedMesh = createInstancedMesh;
+            exports.setInstanceTransform = setInstanceTransform;
+            exports.updateInstances = updateInstances;
+            exports.loadSound = loadSound;
+            exports.playSound = playSound;
+            exports.playSoundAt = playSoundAt;
+            exports.stopSound = stopSound;
+            exports.createPoints = createPoints;
+            exports.setPointsSize = setPointsSize;
+            exports.setPointsSizeAttenuation = setPointsSizeAttenuation;
+            exports.createLine = createLine;
+            exports.createLine2 = createLine2;
+            exports.setLineColor = setLineColor;
+            exports.setDashParams = setDashParams;
+            exports.getBoundingBox = getBoundingBox;
+            exports.getBoundingSphere = getBoundingSphere;
+            exports.isInFrustum = isInFrustum;
+            exports.loadModel = loadModel;
+            exports.modelIsReady = modelIsReady;
+            exports.getModelAnimationCount = getModelAnimationCount;
+            exports.getModelAnimationName = getModelAnimationName;
+            exports.playAnimation = playAnimation;
+            exports.stopAnimation = stopAnimation;
+            exports.setAnimationTime = setAnimationTime;
+            exports.getAnimationDuration = getAnimationDuration;
+            exports.setMorphWeight = setMorphWeight;
+            exports.getMorphTargetCount = getMorphTargetCount;
+            exports.getCanvasSize = getCanvasSize;
+            exports.captureFrame = captureFrame;
+            exports.setRendererSize = setRendererSize;
+            exports.setRotationOrder = setRotationOrder;
+            exports.setVisible = setVisible;
+            exports.setBillboard = setBillboard;
+            exports.createCamera = createCamera;
+            exports.createRenderTarget = createRenderTarget;
+            exports.renderToTarget = renderToTarget;
+            exports.setCameraPositionFor = setCameraPositionFor;
+            exports.cameraLookAtFor = cameraLookAtFor;
+            exports.setObjectLayer = setObjectLayer;
+            exports.setCameraLayers = setCameraLayers;
+            exports.setActiveCamera = setActiveCamera;
+            exports.loadCubeMap = loadCubeMap;
+            exports.setSceneBackground = setSceneBackground;
+            exports.setSceneEnvironment = setSceneEnvironment;
+            exports.createGrid = createGrid;
+            exports.createAxesHelper = createAxesHelper;
+            exports.setPixelRatio = setPixelRatio;
+            exports.setAntialias = setAntialias;
+            exports.setToneMapping = setToneMapping;
+            exports.setSSAO = setSSAO;
+            exports.setBloom = setBloom;
+            exports.createTextSprite = createTextSprite;
+            exports.updateTextSprite = updateTextSprite;
             return exports
         }
     }
 }
 
+/** ERRORS */
+
+/**
+ * Negative status codes returned by bindings called from WASM.
+ * Must be kept in sync with `ThreeError::from_code` on the Rust side.
+ */
+export enum ErrorCode {
+    UnknownObject = -1,
+    InvalidArgument = -2,
+    JsException = -3,
+    NotInitialized = -4,
+}
+
 /** CLASSES */
 export enum MaterialClass {
     MeshBasicMaterial = 1001,
@@ -456,6 +3913,10 @@ export enum MaterialClass {
     LineBasicMaterial = 1010,
     LineDashedMaterial = 1011,
     PointsMaterial = 1012,
+    MeshMatcapMaterial = 1013,
+    MeshDistanceMaterial = 1014,
+    ShadowMaterial = 1015,
+    SpriteMaterial = 1016,
 }
 
 export enum GeometryClass {
@@ -465,13 +3926,56 @@ export enum GeometryClass {
     CylinderGeometry = 2004,
     ConeGeometry = 2005,
     TorusGeometry = 2006,
+    TetrahedronGeometry = 2007,
+    IcosahedronGeometry = 2008,
+    OctahedronGeometry = 2009,
+    DodecahedronGeometry = 2010,
+    RingGeometry = 2011,
+    TorusKnotGeometry = 2012,
+    CircleGeometry = 2013,
+}
+
+export enum LightClass {
+    AmbientLight = 3001,
+    DirectionalLight = 3002,
+    PointLight = 3003,
+    SpotLight = 3004,
+    HemisphereLight = 3005,
+}
+
+/** Kinds of events returned by pollEvent. Must be kept in sync with `Event` on the Rust side. */
+export enum EventKind {
+    TextureLoaded = 1,
+    SoundFinished = 2,
+    WindowBlur = 3,
+    WindowFocus = 4,
+}
+
+export enum RotationOrder {
+    XYZ = 4001,
+    YXZ = 4002,
+    ZXY = 4003,
+    ZYX = 4004,
+    YZX = 4005,
+    XZY = 4006,
 }
 
 /** INPUT */
+
+/** Maps `KeyboardEvent.key` to a bit. Must match the `KeysSet` constants on the Rust side. */
 const keys: Record<string, number> = {
-    w: 1 << 0,
-    a: 1 << 1,
-    s: 1 << 2,
-    d: 1 << 3,
-    space: 1 << 4,
+    a: 1 << 0,
+    s: 1 << 1,
+    d: 1 << 2,
+    w: 1 << 3,
+    ' ': 1 << 4,
+    ArrowUp: 1 << 5,
+    ArrowDown: 1 << 6,
+    ArrowLeft: 1 << 7,
+    ArrowRight: 1 << 8,
+    Shift: 1 << 9,
+    Control: 1 << 10,
+    Alt: 1 << 11,
+    Escape: 1 << 12,
+    Enter: 1 << 13,
 }
@@ -0,0 +1,170 @@
+//! Keyframed animation of values and transforms, for moving objects along a path without
+//! loading a glTF model:
+//!
+//! ```ignore
+//! let mut path = TransformTrack::default();
+//! path.position.insert(0.0, Vec3::ZERO);
+//! path.position.insert(2.0, Vec3::new(5.0, 0.0, 0.0));
+//! path.rotation.insert(2.0, Quat::from_axis_angle(Vec3::Y, PI));
+//! let mut elapsed = 0.0;
+//! ctx::run_loop(move |delta| {
+//!     elapsed += delta;
+//!     path.apply(obj, elapsed).is_ok()
+//! })?;
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{Euler, Quat, Transform, Vec3, tween::Lerp};
+#[cfg(feature = "ffi")]
+use crate::{ObjectId, ThreeError};
+
+/// Keyframes `(time, value)` sorted by time, interpolated with [`Lerp`] (which is a slerp
+/// for [`Quat`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Track<T> {
+    keyframes: Vec<(f32, T)>,
+}
+
+impl<T> Default for Track<T> {
+    fn default() -> Self {
+        Track {
+            keyframes: Vec::new(),
+        }
+    }
+}
+
+impl<T: Lerp> Track<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track with the given keyframes, which don't have to be sorted.
+    pub fn from_keyframes(keyframes: impl IntoIterator<Item = (f32, T)>) -> Self {
+        let mut keyframes: Vec<(f32, T)> = keyframes.into_iter().collect();
+        keyframes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Track { keyframes }
+    }
+
+    /// Adds a keyframe, keeping the track sorted. A keyframe at the same time as an existing
+    /// one is placed after it.
+    pub fn insert(&mut self, time: f32, value: T) -> &mut Self {
+        let index = self.keyframes.partition_point(|(t, _)| *t <= time);
+        self.keyframes.insert(index, (time, value));
+        self
+    }
+
+    pub fn keyframes(&self) -> &[(f32, T)] {
+        &self.keyframes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    /// Time of the last keyframe, or 0 for an empty track.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |(t, _)| *t)
+    }
+
+    /// Value at time `t`. Times before the first keyframe give its value, times after the
+    /// last keyframe give the last value.
+    ///
+    /// # Panics
+    ///
+    /// If the track has no keyframes.
+    pub fn sample(&self, t: f32) -> T {
+        self.try_sample(t).expect("sampled an empty track")
+    }
+
+    /// Like [`Track::sample`], `None` for an empty track.
+    pub fn try_sample(&self, t: f32) -> Option<T> {
+        let next = self.keyframes.partition_point(|(time, _)| *time <= t);
+        if next == 0 {
+            return self.keyframes.first().map(|(_, v)| *v);
+        }
+        let (t0, v0) = self.keyframes[next - 1];
+        let Some(&(t1, v1)) = self.keyframes.get(next) else {
+            return Some(v0);
+        };
+        Some(v0.lerp(v1, (t - t0) / (t1 - t0)))
+    }
+}
+
+/// Separate position, rotation and scale tracks of one object. Empty tracks leave the
+/// corresponding part of [`Transform::IDENTITY`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransformTrack {
+    pub position: Track<Vec3>,
+    pub rotation: Track<Quat>,
+    pub scale: Track<Vec3>,
+}
+
+impl TransformTrack {
+    /// Time of the last keyframe of any track.
+    pub fn duration(&self) -> f32 {
+        self.position
+            .duration()
+            .max(self.rotation.duration())
+            .max(self.scale.duration())
+    }
+
+    pub fn sample(&self, t: f32) -> Transform {
+        let identity = Transform::IDENTITY;
+        let rotation = self
+            .rotation
+            .try_sample(t)
+            .map(Euler::from_quat)
+            .map_or(identity.rotation, |e| Vec3::new(e.x, e.y, e.z));
+        Transform {
+            position: self.position.try_sample(t).unwrap_or(identity.position),
+            rotation,
+            scale: self.scale.try_sample(t).unwrap_or(identity.scale),
+        }
+    }
+
+    /// Pushes the transform at time `t` to the object with
+    /// [`ctx::set_transform`](crate::ctx::set_transform).
+    #[cfg(feature = "ffi")]
+    pub fn apply(&self, object_id: ObjectId, t: f32) -> Result<(), ThreeError> {
+        crate::ctx::set_transform(object_id, &self.sample(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_clamps_outside_keyframes() {
+        let track = Track::from_keyframes([(2.0, 10.0), (1.0, 0.0), (3.0, 5.0)]);
+        assert_eq!(track.sample(0.0), 0.0);
+        assert_eq!(track.sample(1.5), 5.0);
+        assert_eq!(track.sample(2.5), 7.5);
+        assert_eq!(track.sample(3.0), 5.0);
+        assert_eq!(track.sample(10.0), 5.0);
+        assert_eq!(Track::<f32>::new().try_sample(1.0), None);
+    }
+
+    #[test]
+    fn transform_track_slerps_rotation() {
+        let mut track = TransformTrack::default();
+        track
+            .position
+            .insert(0.0, Vec3::ZERO)
+            .insert(2.0, Vec3::new(4.0, 0.0, 0.0));
+        track.rotation.insert(0.0, Quat::IDENTITY).insert(
+            2.0,
+            Quat::from_axis_angle(Vec3::Y, core::f32::consts::FRAC_PI_2),
+        );
+
+        let mid = track.sample(1.0);
+        assert_eq!(mid.position, Vec3::new(2.0, 0.0, 0.0));
+        assert!((mid.rotation.y - core::f32::consts::FRAC_PI_4).abs() < 1e-5);
+        assert_eq!(mid.scale, Transform::IDENTITY.scale);
+
+        let end = track.sample(5.0);
+        assert_eq!(end.position, Vec3::new(4.0, 0.0, 0.0));
+        assert_eq!(track.duration(), 2.0);
+    }
+}
@@ -0,0 +1,133 @@
+//! Fluent setup of objects, instead of creating one and calling a setter per property:
+//!
+//! ```ignore
+//! let crate_ = ObjectBuilder::mesh(GeometryClass::BoxGeometry, MaterialClass::MeshStandardMaterial)
+//!     .position(Vec3::new(2.0, 0.5, 0.0))
+//!     .color(Color::rgb(160, 110, 60))
+//!     .build()?;
+//! ```
+
+use crate::{Color, GeometryClass, MaterialClass, ObjectId, ThreeError, Vec3, ctx};
+
+/// Creates a mesh, applies the properties which were set and adds it to the scene.
+///
+/// Position and rotation go through [`ctx::set_position_v`] and [`ctx::set_rotation_v`], so
+/// they are in the coordinates chosen with [`ctx::set_coordinate_system`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[must_use = "the object is only created by `build`"]
+pub struct ObjectBuilder {
+    geometry: GeometryClass,
+    material: MaterialClass,
+    position: Option<Vec3>,
+    rotation: Option<Vec3>,
+    scale: Option<Vec3>,
+    color: Option<Color>,
+}
+
+impl ObjectBuilder {
+    pub fn mesh(geometry: GeometryClass, material: MaterialClass) -> Self {
+        ObjectBuilder {
+            geometry,
+            material,
+            position: None,
+            rotation: None,
+            scale: None,
+            color: None,
+        }
+    }
+
+    pub fn position(mut self, position: Vec3) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Euler rotation in radians, see [`ctx::set_rotation_v`].
+    pub fn rotation(mut self, rotation: Vec3) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    pub fn scale(mut self, scale: Vec3) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Creates the object and adds it to the scene. If any step fails, the object is
+    /// disposed again and the error returned.
+    pub fn build(self) -> Result<ObjectId, ThreeError> {
+        let id = ctx::create_object(self.geometry, self.material)?;
+        match self.apply(id) {
+            Ok(()) => Ok(id),
+            Err(err) => {
+                let _ = ctx::dispose_object(id);
+                Err(err)
+            }
+        }
+    }
+
+    fn apply(&self, id: ObjectId) -> Result<(), ThreeError> {
+        if let Some(position) = self.position {
+            ctx::set_position_v(id, position)?;
+        }
+        if let Some(rotation) = self.rotation {
+            ctx::set_rotation_v(id, rotation)?;
+        }
+        if let Some(scale) = self.scale {
+            ctx::set_scale_v(id, scale)?;
+        }
+        if let Some(color) = self.color {
+            ctx::set_material_color(id, color)?;
+        }
+        ctx::add_object_to_scene(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{self, Call};
+
+    #[test]
+    fn build_emits_create_setters_and_add() {
+        test::set_next_id(4);
+        test::take_calls();
+        let id = ObjectBuilder::mesh(GeometryClass::BoxGeometry, MaterialClass::MeshBasicMaterial)
+            .scale(Vec3::new(2.0, 2.0, 2.0))
+            .position(Vec3::new(1.0, 0.0, -1.0))
+            .color(Color::RED)
+            .build()
+            .unwrap();
+        assert_eq!(id.raw(), 4);
+        assert_eq!(
+            test::take_calls(),
+            [
+                Call::CreateObject {
+                    geometry: GeometryClass::BoxGeometry,
+                    material: MaterialClass::MeshBasicMaterial,
+                },
+                Call::SetPosition {
+                    object_id: 4,
+                    x: 1.0,
+                    y: 0.0,
+                    z: -1.0,
+                },
+                Call::SetScale {
+                    object_id: 4,
+                    x: 2.0,
+                    y: 2.0,
+                    z: 2.0,
+                },
+                Call::SetMaterialColor {
+                    object_id: 4,
+                    color: Color::RED.as_i32(),
+                },
+                Call::AddObjectToScene { object_id: 4 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{ThreeError, Vec3, ctx, float};
+
+/// Chase camera which trails a target at a fixed offset, easing towards it instead of
+/// snapping so that sudden moves of the target look smooth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FollowCamera {
+    /// Position of the camera relative to the target.
+    pub offset: Vec3,
+    /// How fast the camera catches up, per second. Larger values follow more tightly;
+    /// after `1 / smoothing` seconds about two thirds of the distance are covered.
+    pub smoothing: f32,
+    position: Option<Vec3>,
+}
+
+impl FollowCamera {
+    pub const fn new(offset: Vec3, smoothing: f32) -> Self {
+        FollowCamera {
+            offset,
+            smoothing,
+            position: None,
+        }
+    }
+
+    /// Moves the active camera towards `target + offset` and points it at `target`.
+    ///
+    /// `delta` is the frame time in seconds, which makes the motion independent of the frame
+    /// rate. The first update places the camera at its goal directly.
+    pub fn update(&mut self, target: Vec3, delta: f32) -> Result<(), ThreeError> {
+        let goal = target + self.offset;
+        let position = match self.position {
+            Some(position) => position.lerp(goal, 1.0 - float::exp(-self.smoothing * delta)),
+            None => goal,
+        };
+        self.position = Some(position);
+
+        ctx::set_camera_position(position.x, position.y, position.z)?;
+        ctx::camera_look_at(target.x, target.y, target.z)
+    }
+
+    /// Where the camera was placed by the last [`FollowCamera::update`].
+    pub fn position(&self) -> Option<Vec3> {
+        self.position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::test::{self, Call};
+
+    fn camera_positions(calls: &[Call]) -> Vec<Vec3> {
+        calls
+            .iter()
+            .filter_map(|call| match *call {
+                Call::SetCameraPosition { x, y, z } => Some(Vec3::new(x, y, z)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn converges_towards_target() {
+        let offset = Vec3::new(0.0, 2.0, 5.0);
+        let mut camera = FollowCamera::new(offset, 4.0);
+        camera.update(Vec3::ZERO, 0.016).unwrap();
+
+        let target = Vec3::new(10.0, 0.0, 0.0);
+        let goal = target + offset;
+        test::take_calls();
+        for _ in 0..60 {
+            camera.update(target, 0.016).unwrap();
+        }
+
+        let calls = test::take_calls();
+        assert!(calls.contains(&Call::CameraLookAt {
+            x: 10.0,
+            y: 0.0,
+            z: 0.0
+        }));
+        let distances: Vec<f32> = camera_positions(&calls)
+            .into_iter()
+            .map(|p| (goal - p).x.abs())
+            .collect();
+        assert_eq!(distances.len(), 60);
+        assert!(distances.windows(2).all(|w| w[1] < w[0]));
+        assert!(distances[59] < 0.5);
+    }
+}
@@ -0,0 +1,339 @@
+//! Collision helpers working on [`Aabb`]s. They don't touch the JS side, so they can be fed
+//! with boxes from `ctx::bounding_box` or computed by the game itself.
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::ObjectId;
+use crate::{Aabb, Vec3};
+
+/// Returns the minimum translation vector which, added to `a`, separates it from `b`,
+/// or `None` if the boxes don't overlap. Boxes which only touch don't overlap.
+pub fn aabb_overlap(a: &Aabb, b: &Aabb) -> Option<Vec3> {
+    let axes = [
+        (a.min.x, a.max.x, b.min.x, b.max.x),
+        (a.min.y, a.max.y, b.min.y, b.max.y),
+        (a.min.z, a.max.z, b.min.z, b.max.z),
+    ];
+
+    let mut best: Option<(usize, f32)> = None;
+    for (axis, (a_min, a_max, b_min, b_max)) in axes.into_iter().enumerate() {
+        // how far `a` has to move towards -axis / +axis to leave `b`
+        let push_neg = a_max - b_min;
+        let push_pos = b_max - a_min;
+        if push_neg <= 0.0 || push_pos <= 0.0 {
+            return None;
+        }
+
+        let push = if push_neg < push_pos {
+            -push_neg
+        } else {
+            push_pos
+        };
+        if best.is_none_or(|(_, best_push)| push.abs() < best_push.abs()) {
+            best = Some((axis, push));
+        }
+    }
+
+    best.map(|(axis, push)| match axis {
+        0 => Vec3::new(push, 0.0, 0.0),
+        1 => Vec3::new(0.0, push, 0.0),
+        _ => Vec3::new(0.0, 0.0, push),
+    })
+}
+
+/// Sweeps `moving` along `velocity` and returns the time of impact with `static_`, as a
+/// fraction of `velocity` between 0 and 1, or `None` if they don't collide within that move.
+/// Boxes which already overlap collide at time 0.
+pub fn sweep_aabb(moving: &Aabb, velocity: Vec3, static_: &Aabb) -> Option<f32> {
+    let axes = [
+        (
+            moving.min.x,
+            moving.max.x,
+            static_.min.x,
+            static_.max.x,
+            velocity.x,
+        ),
+        (
+            moving.min.y,
+            moving.max.y,
+            static_.min.y,
+            static_.max.y,
+            velocity.y,
+        ),
+        (
+            moving.min.z,
+            moving.max.z,
+            static_.min.z,
+            static_.max.z,
+            velocity.z,
+        ),
+    ];
+
+    let mut t_entry = f32::NEG_INFINITY;
+    let mut t_exit = f32::INFINITY;
+    for (m_min, m_max, s_min, s_max, v) in axes {
+        if v == 0.0 {
+            if m_max <= s_min || m_min >= s_max {
+                return None;
+            }
+            continue;
+        }
+
+        let (entry, exit) = if v > 0.0 {
+            ((s_min - m_max) / v, (s_max - m_min) / v)
+        } else {
+            ((s_max - m_min) / v, (s_min - m_max) / v)
+        };
+        t_entry = t_entry.max(entry);
+        t_exit = t_exit.min(exit);
+    }
+
+    if t_entry > t_exit || t_entry > 1.0 || t_exit <= 0.0 {
+        return None;
+    }
+    Some(t_entry.max(0.0))
+}
+
+/// Broad phase for many objects: boxes are sorted into a uniform grid of cubes, so a query
+/// only has to look at the boxes sharing a cell with it instead of all of them.
+///
+/// Rebuild it every frame with [`SpatialHash::clear`] and [`SpatialHash::insert`], then run
+/// the exact test, e.g. [`aabb_overlap`], only on the pairs returned by
+/// [`SpatialHash::query`]. The cell size should be about the size of a typical object:
+/// large boxes are stored in every cell they cover, up to [`MAX_CELLS_PER_BOX`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    cell_size: f32,
+    entries: Vec<(ObjectId, Aabb)>,
+    /// Indices into `entries`, by cell coordinates.
+    cells: BTreeMap<(i32, i32, i32), Vec<usize>>,
+    /// Indices into `entries` of boxes covering too many cells (or with NaN bounds), which
+    /// every query checks.
+    oversized: Vec<usize>,
+}
+
+/// Most cells a box is stored in by [`SpatialHash`]. Bigger boxes, e.g. a ground plane with
+/// infinite extents, are kept in a single list checked by every query instead, and bigger
+/// query boxes check all objects.
+#[cfg(feature = "alloc")]
+pub const MAX_CELLS_PER_BOX: u64 = 4096;
+
+#[cfg(feature = "alloc")]
+type Cell = (i32, i32, i32);
+
+#[cfg(feature = "alloc")]
+impl SpatialHash {
+    /// Hash with cubic cells of `cell_size` world units, which must be positive.
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size > 0.0, "cell size must be positive");
+        SpatialHash {
+            cell_size,
+            entries: Vec::new(),
+            cells: BTreeMap::new(),
+            oversized: Vec::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    fn cell_of(&self, p: Vec3) -> Cell {
+        fn floor(x: f32) -> i32 {
+            let truncated = x as i32;
+            if (truncated as f32) > x {
+                // saturating, as -inf becomes i32::MIN
+                truncated.saturating_sub(1)
+            } else {
+                truncated
+            }
+        }
+        let size = self.cell_size;
+        (floor(p.x / size), floor(p.y / size), floor(p.z / size))
+    }
+
+    /// First and last cell the box touches, or `None` if that is more than
+    /// [`MAX_CELLS_PER_BOX`] cells or the box has NaN bounds.
+    fn cell_range(&self, aabb: &Aabb) -> Option<(Cell, Cell)> {
+        let bounds = [
+            aabb.min.x, aabb.min.y, aabb.min.z, aabb.max.x, aabb.max.y, aabb.max.z,
+        ];
+        if bounds.iter().any(|x| x.is_nan()) {
+            return None;
+        }
+        let (min, max) = (self.cell_of(aabb.min), self.cell_of(aabb.max));
+        let len = |lo: i32, hi: i32| (hi as i64 - lo as i64 + 1).max(0) as u64;
+        let count = len(min.0, max.0)
+            .saturating_mul(len(min.1, max.1))
+            .saturating_mul(len(min.2, max.2));
+        (count <= MAX_CELLS_PER_BOX).then_some((min, max))
+    }
+
+    /// Every cell between `min` and `max`.
+    fn cells_of((min, max): (Cell, Cell)) -> impl Iterator<Item = Cell> {
+        (min.0..=max.0).flat_map(move |x| {
+            (min.1..=max.1).flat_map(move |y| (min.2..=max.2).map(move |z| (x, y, z)))
+        })
+    }
+
+    /// Adds the object with its (world-space) box. Inserting an object twice stores it twice.
+    pub fn insert(&mut self, id: ObjectId, aabb: Aabb) {
+        let index = self.entries.len();
+        self.entries.push((id, aabb));
+        match self.cell_range(&aabb) {
+            Some(range) => {
+                for cell in Self::cells_of(range) {
+                    self.cells.entry(cell).or_default().push(index);
+                }
+            }
+            None => self.oversized.push(index),
+        }
+    }
+
+    /// Removes all objects, e.g. before inserting them again at their new positions.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.cells.clear();
+        self.oversized.clear();
+    }
+
+    /// Objects whose boxes intersect `aabb` (touching included), each returned once, in the
+    /// order they were inserted.
+    pub fn query(&self, aabb: &Aabb) -> impl Iterator<Item = ObjectId> + '_ {
+        let mut candidates: Vec<usize> = match self.cell_range(aabb) {
+            Some(range) => Self::cells_of(range)
+                .filter_map(|cell| self.cells.get(&cell))
+                .flatten()
+                .chain(&self.oversized)
+                .copied()
+                .collect(),
+            None => (0..self.entries.len()).collect(),
+        };
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let aabb = *aabb;
+        candidates.into_iter().filter_map(move |index| {
+            let (id, other) = self.entries[index];
+            other.intersects(&aabb).then_some(id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aabb(min: (f32, f32, f32), max: (f32, f32, f32)) -> Aabb {
+        Aabb::new(
+            Vec3::new(min.0, min.1, min.2),
+            Vec3::new(max.0, max.1, max.2),
+        )
+    }
+
+    #[test]
+    fn no_overlap() {
+        let a = aabb((0.0, 0.0, 0.0), (1.0, 1.0, 1.0));
+        let b = aabb((2.0, 0.0, 0.0), (3.0, 1.0, 1.0));
+        assert_eq!(aabb_overlap(&a, &b), None);
+        assert_eq!(sweep_aabb(&a, Vec3::new(0.5, 0.0, 0.0), &b), None);
+        assert_eq!(sweep_aabb(&a, Vec3::new(0.0, 5.0, 0.0), &b), None);
+    }
+
+    #[test]
+    fn corner_touching() {
+        let a = aabb((0.0, 0.0, 0.0), (1.0, 1.0, 1.0));
+        let b = aabb((1.0, 1.0, 1.0), (2.0, 2.0, 2.0));
+        assert_eq!(aabb_overlap(&a, &b), None);
+        assert_eq!(sweep_aabb(&a, Vec3::new(-1.0, -1.0, -1.0), &b), None);
+        assert_eq!(sweep_aabb(&a, Vec3::new(1.0, 1.0, 1.0), &b), Some(0.0));
+    }
+
+    #[test]
+    fn partial_overlap_pushes_along_shallowest_axis() {
+        let a = aabb((0.0, 0.0, 0.0), (2.0, 2.0, 2.0));
+        let b = aabb((1.5, 0.5, 0.5), (3.0, 3.0, 3.0));
+        assert_eq!(aabb_overlap(&a, &b), Some(Vec3::new(-0.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn full_containment() {
+        let inner = aabb((1.0, 1.0, 1.0), (2.0, 2.0, 2.0));
+        let outer = aabb((0.0, 0.0, 0.0), (10.0, 10.0, 10.0));
+        // nearest way out of `outer` is through its min faces
+        assert_eq!(
+            aabb_overlap(&inner, &outer),
+            Some(Vec3::new(-2.0, 0.0, 0.0))
+        );
+        assert_eq!(aabb_overlap(&outer, &inner), Some(Vec3::new(2.0, 0.0, 0.0)));
+        assert_eq!(sweep_aabb(&inner, Vec3::X, &outer), Some(0.0));
+    }
+
+    #[test]
+    fn sweep_time_of_impact() {
+        let a = aabb((0.0, 0.0, 0.0), (1.0, 1.0, 1.0));
+        let b = aabb((3.0, 0.0, 0.0), (4.0, 1.0, 1.0));
+        assert_eq!(sweep_aabb(&a, Vec3::new(4.0, 0.0, 0.0), &b), Some(0.5));
+        assert_eq!(sweep_aabb(&b, Vec3::new(-4.0, 0.0, 0.0), &a), Some(0.5));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn spatial_hash_skips_distant_cells() {
+        use std::vec::Vec;
+
+        let mut hash = SpatialHash::new(2.0);
+        let (near, touching, far, big) = (
+            ObjectId::new(1),
+            ObjectId::new(2),
+            ObjectId::new(3),
+            ObjectId::new(4),
+        );
+        hash.insert(near, aabb((0.5, 0.5, 0.5), (1.5, 1.5, 1.5)));
+        hash.insert(touching, aabb((1.0, 0.0, 0.0), (3.0, 1.0, 1.0)));
+        hash.insert(far, aabb((100.0, 0.0, 0.0), (101.0, 1.0, 1.0)));
+        hash.insert(big, aabb((-10.0, -10.0, -10.0), (-3.0, 10.0, 10.0)));
+
+        let query = aabb((0.0, 0.0, 0.0), (1.0, 1.0, 1.0));
+        assert_eq!(hash.query(&query).collect::<Vec<_>>(), [near, touching]);
+        // negative coordinates land in their own cells
+        let query = aabb((-4.0, 0.0, 0.0), (-3.5, 1.0, 1.0));
+        assert_eq!(hash.query(&query).collect::<Vec<_>>(), [big]);
+        let query = aabb((50.0, 0.0, 0.0), (51.0, 1.0, 1.0));
+        assert_eq!(hash.query(&query).count(), 0);
+
+        hash.clear();
+        assert_eq!(
+            hash.query(&aabb((100.0, 0.0, 0.0), (101.0, 1.0, 1.0)))
+                .count(),
+            0
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn spatial_hash_keeps_huge_boxes_out_of_the_grid() {
+        use std::vec::Vec;
+
+        let mut hash = SpatialHash::new(0.5);
+        let (ground, crate_, broken) = (ObjectId::new(1), ObjectId::new(2), ObjectId::new(3));
+        hash.insert(
+            ground,
+            aabb((f32::MIN, -1.0, f32::MIN), (f32::MAX, 0.0, f32::MAX)),
+        );
+        hash.insert(crate_, aabb((1000.0, 0.0, 1000.0), (1001.0, 1.0, 1001.0)));
+        hash.insert(broken, aabb((f32::NAN, 0.0, 0.0), (1.0, 1.0, 1.0)));
+        assert!(hash.cells.len() <= MAX_CELLS_PER_BOX as usize);
+
+        let query = aabb((1000.5, -0.5, 1000.5), (1000.6, 0.5, 1000.6));
+        assert_eq!(hash.query(&query).collect::<Vec<_>>(), [ground, crate_]);
+        let query = aabb((0.0, 0.5, 0.0), (1.0, 1.0, 1.0));
+        assert_eq!(hash.query(&query).count(), 0);
+        // a query as big as the ground falls back to checking every box
+        let query = aabb((-1e30, 0.5, -1e30), (1e30, 1.0, 1e30));
+        assert_eq!(hash.query(&query).collect::<Vec<_>>(), [crate_]);
+    }
+}
@@ -0,0 +1,183 @@
+/// RGB color packed as `0xRRGGBB`, the same format three.js accepts for hex colors.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Color(u32);
+
+impl Color {
+    pub const BLACK: Color = Color::hex(0x000000);
+    pub const WHITE: Color = Color::hex(0xFFFFFF);
+    pub const RED: Color = Color::hex(0xFF0000);
+    pub const GREEN: Color = Color::hex(0x00FF00);
+    pub const BLUE: Color = Color::hex(0x0000FF);
+    pub const YELLOW: Color = Color::hex(0xFFFF00);
+    pub const CYAN: Color = Color::hex(0x00FFFF);
+    pub const MAGENTA: Color = Color::hex(0xFF00FF);
+
+    #[inline(always)]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color(((r as u32) << 16) | ((g as u32) << 8) | (b as u32))
+    }
+
+    /// Creates a color from a `0xRRGGBB` value. Bits above the lowest 24 are ignored.
+    #[inline(always)]
+    pub const fn hex(value: u32) -> Self {
+        Color(value & 0xFF_FFFF)
+    }
+
+    #[inline(always)]
+    pub const fn r(&self) -> u8 {
+        ((self.0 >> 16) & 0xFF) as u8
+    }
+
+    #[inline(always)]
+    pub const fn g(&self) -> u8 {
+        ((self.0 >> 8) & 0xFF) as u8
+    }
+
+    #[inline(always)]
+    pub const fn b(&self) -> u8 {
+        (self.0 & 0xFF) as u8
+    }
+
+    /// Creates a color from hue (degrees, wrapping), saturation and lightness (both clamped to
+    /// `0..=1`), like `THREE.Color.setHSL` but with the hue in degrees.
+    pub fn hsl(h: f32, s: f32, l: f32) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let sector = wrap_hue(h) / 60.0;
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        // `as` saturates, and adding 0.5 rounds the non-negative result
+        let channel = |c: f32| ((c + m) * 255.0 + 0.5) as u8;
+        Color::rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// Hue (degrees in `0..360`), saturation and lightness (both `0..=1`). Grays have hue 0.
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let [r, g, b] = self.to_rgb_f32();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return (0.0, 0.0, l);
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            (g - b) / d
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (wrap_hue(h * 60.0), s.min(1.0), l)
+    }
+
+    /// The same color with lightness `l` (`0` is black, `1` is white).
+    pub fn with_lightness(self, l: f32) -> Color {
+        let (h, s, _) = self.to_hsl();
+        Color::hsl(h, s, l)
+    }
+
+    /// The same color with saturation `s` (`0` is gray).
+    pub fn with_saturation(self, s: f32) -> Color {
+        let (h, _, l) = self.to_hsl();
+        Color::hsl(h, s, l)
+    }
+
+    /// Moves the hue around the color wheel, wrapping past 360°.
+    pub fn rotate_hue(self, degrees: f32) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::hsl(h + degrees, s, l)
+    }
+
+    /// Interpolates each channel between `self` (`t = 0`) and `other` (`t = 1`).
+    pub fn lerp(self, other: Color, t: f32) -> Color {
+        fn channel(a: u8, b: u8, t: f32) -> u8 {
+            // `as` saturates, and adding 0.5 rounds the non-negative result
+            (crate::math::lerp(a as f32, b as f32, t) + 0.5) as u8
+        }
+        Color::rgb(
+            channel(self.r(), other.r(), t),
+            channel(self.g(), other.g(), t),
+            channel(self.b(), other.b(), t),
+        )
+    }
+
+    /// The channels as `[r, g, b]` between 0.0 and 1.0, still in sRGB.
+    pub fn to_rgb_f32(self) -> [f32; 3] {
+        [self.r(), self.g(), self.b()].map(|c| c as f32 / 255.0)
+    }
+
+    /// Returns the color in the form expected by the FFI functions.
+    #[inline(always)]
+    pub const fn as_i32(&self) -> i32 {
+        self.0 as i32
+    }
+}
+
+/// Maps any angle in degrees into `0..360`.
+fn wrap_hue(degrees: f32) -> f32 {
+    let h = degrees % 360.0;
+    if h < 0.0 { h + 360.0 } else { h }
+}
+
+impl From<i32> for Color {
+    fn from(value: i32) -> Self {
+        Color::hex(value as u32)
+    }
+}
+
+impl From<u32> for Color {
+    fn from(value: u32) -> Self {
+        Color::hex(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_rgb_f32_scales_channels() {
+        assert_eq!(Color::rgb(255, 0, 51).to_rgb_f32(), [1.0, 0.0, 0.2]);
+    }
+
+    #[test]
+    fn lerp_endpoints_and_midpoint() {
+        let (a, b) = (Color::rgb(0, 100, 255), Color::rgb(255, 200, 0));
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Color::rgb(128, 150, 128));
+    }
+
+    #[test]
+    fn hsl_primaries_and_round_trip() {
+        assert_eq!(Color::hsl(0.0, 1.0, 0.5), Color::RED);
+        assert_eq!(Color::hsl(120.0, 1.0, 0.5), Color::GREEN);
+        assert_eq!(Color::hsl(240.0, 1.0, 0.5), Color::BLUE);
+        assert_eq!(Color::hsl(77.0, 0.0, 1.0), Color::WHITE);
+
+        let color = Color::rgb(30, 144, 255);
+        let (h, s, l) = color.to_hsl();
+        assert_eq!(Color::hsl(h, s, l), color);
+        assert_eq!(Color::RED.with_lightness(0.0), Color::BLACK);
+        assert_eq!(Color::RED.with_saturation(0.0), Color::rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn hue_rotation_wraps() {
+        assert_eq!(Color::RED.rotate_hue(360.0), Color::RED);
+        assert_eq!(Color::RED.rotate_hue(480.0), Color::GREEN);
+        assert_eq!(Color::GREEN.rotate_hue(-120.0), Color::RED);
+        assert_eq!(Color::BLUE.rotate_hue(-480.0), Color::GREEN);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{MovementMapping, ThreeError, Vec3, ctx, float};
+
+/// Highest pitch, just short of straight up so that the look-at direction never lines up
+/// with the camera's up vector.
+const MAX_PITCH: f32 = 89.0 * core::f32::consts::PI / 180.0;
+
+/// First-person camera: the mouse turns the view and WSAD walk on the horizontal plane.
+///
+/// Yaw is measured around +Y, with 0 looking along -Z (where the default three.js camera
+/// looks). Pitch is positive looking up and stays within ±89°.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FirstPersonController {
+    /// Walking speed in world units per second.
+    pub speed: f32,
+    /// Radians turned per pixel of mouse movement.
+    pub sensitivity: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub position: Vec3,
+}
+
+impl FirstPersonController {
+    pub const fn new(position: Vec3, speed: f32, sensitivity: f32) -> Self {
+        FirstPersonController {
+            speed,
+            sensitivity,
+            yaw: 0.0,
+            pitch: 0.0,
+            position,
+        }
+    }
+
+    /// Direction the camera looks in, of length 1.
+    pub fn forward(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = float::sin_cos(self.yaw);
+        let (sin_pitch, cos_pitch) = float::sin_cos(self.pitch);
+        Vec3::new(-sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch)
+    }
+
+    /// Reads the mouse movement and the held keys, turns and moves accordingly, then places
+    /// the active camera.
+    ///
+    /// `delta` is the frame time in seconds. As the mouse movement is consumed, nothing else
+    /// should call [`ctx::get_mouse_movement`] in the same frame.
+    pub fn update(&mut self, delta: f32) -> Result<(), ThreeError> {
+        let mouse = ctx::get_mouse_movement();
+        // moving the mouse right or down turns right or down
+        self.yaw -= mouse.x as f32 * self.sensitivity;
+        self.pitch = (self.pitch - mouse.y as f32 * self.sensitivity).clamp(-MAX_PITCH, MAX_PITCH);
+
+        let (sin_yaw, cos_yaw) = float::sin_cos(self.yaw);
+        let mapping = MovementMapping {
+            forward: Vec3::new(-sin_yaw, 0.0, -cos_yaw),
+            right: Vec3::new(cos_yaw, 0.0, -sin_yaw),
+        };
+        let direction = ctx::get_keys_pressed().movement_vector_with(&mapping);
+        self.position = self.position + direction * (self.speed * delta);
+
+        let target = self.position + self.forward();
+        ctx::set_camera_position(self.position.x, self.position.y, self.position.z)?;
+        ctx::camera_look_at(target.x, target.y, target.z)
+    }
+}
+
+/// Radians per second turned with the arrow keys by [`OrbitController`].
+const ORBIT_KEY_SPEED: f32 = core::f32::consts::FRAC_PI_2;
+
+/// Model-viewer camera circling `target`: dragging with the left mouse button (or holding the
+/// arrow keys) orbits around it and scrolling zooms in and out.
+///
+/// Yaw 0 places the camera on the +Z side of the target, and pitch is positive above it,
+/// within ±89° like [`FirstPersonController`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitController {
+    pub target: Vec3,
+    /// Distance of the camera from the target, kept between `min_distance` and
+    /// `max_distance`.
+    pub distance: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    /// Radians turned per pixel of mouse movement.
+    pub sensitivity: f32,
+    /// How much the distance changes per scrolled pixel, relatively: 100 pixels scale it by
+    /// `exp(100 * zoom_sensitivity)`.
+    pub zoom_sensitivity: f32,
+}
+
+impl OrbitController {
+    pub const fn new(target: Vec3, distance: f32, min_distance: f32, max_distance: f32) -> Self {
+        OrbitController {
+            target,
+            distance,
+            min_distance,
+            max_distance,
+            yaw: 0.0,
+            pitch: 0.0,
+            sensitivity: 0.005,
+            zoom_sensitivity: 0.001,
+        }
+    }
+
+    /// Where the camera is placed for the current angles and distance.
+    pub fn position(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = float::sin_cos(self.yaw);
+        let (sin_pitch, cos_pitch) = float::sin_cos(self.pitch);
+        self.target + Vec3::new(sin_yaw * cos_pitch, sin_pitch, cos_yaw * cos_pitch) * self.distance
+    }
+
+    /// Reads the mouse, the scroll wheel and the arrow keys, then places the active camera
+    /// and points it at the target.
+    ///
+    /// `delta` is the frame time in seconds, used for turning with the keys. Like
+    /// [`FirstPersonController::update`] this consumes the mouse movement.
+    pub fn update(&mut self, delta: f32) -> Result<(), ThreeError> {
+        let mouse = ctx::get_mouse_movement();
+        if ctx::get_mouse_buttons().left() {
+            // dragging right or down moves the camera left or up, so the scene follows the mouse
+            self.yaw -= mouse.x as f32 * self.sensitivity;
+            self.pitch += mouse.y as f32 * self.sensitivity;
+        }
+        let keys = ctx::get_keys_pressed();
+        let axis = |positive: bool, negative: bool| positive as i32 as f32 - negative as i32 as f32;
+        self.yaw += axis(keys.right(), keys.left()) * ORBIT_KEY_SPEED * delta;
+        self.pitch += axis(keys.up(), keys.down()) * ORBIT_KEY_SPEED * delta;
+        self.pitch = self.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+
+        let zoom = float::exp(ctx::scroll_delta() * self.zoom_sensitivity);
+        self.distance = (self.distance * zoom).clamp(self.min_distance, self.max_distance);
+
+        let position = self.position();
+        ctx::set_camera_position(position.x, position.y, position.z)?;
+        ctx::camera_look_at(self.target.x, self.target.y, self.target.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{self, Call};
+    use crate::{KeysSet, MouseButtons};
+
+    fn approx_eq(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-3
+    }
+
+    #[test]
+    fn mouse_rotates_the_view() {
+        let mut controller = FirstPersonController::new(Vec3::ZERO, 1.0, 0.01);
+        test::set_keys_pressed(KeysSet(0));
+        test::set_mouse_movement(-157, 0);
+        controller.update(0.016).unwrap();
+        // a quarter turn to the left looks along -X
+        assert!(approx_eq(controller.forward(), Vec3::new(-1.0, 0.0, 0.0)));
+
+        test::set_mouse_movement(0, -10_000);
+        test::take_calls();
+        controller.update(0.016).unwrap();
+        assert!((controller.pitch - MAX_PITCH).abs() < 1e-6);
+        assert!(matches!(
+            test::take_calls().last(),
+            Some(Call::CameraLookAt { y, .. }) if *y > 0.99
+        ));
+    }
+
+    #[test]
+    fn wsad_moves_along_the_view() {
+        let mut controller = FirstPersonController::new(Vec3::new(0.0, 1.7, 0.0), 2.0, 0.01);
+        test::set_mouse_movement(0, 0);
+        test::set_keys_pressed(KeysSet(KeysSet::W));
+        test::take_calls();
+        controller.update(0.5).unwrap();
+        assert!(approx_eq(controller.position, Vec3::new(0.0, 1.7, -1.0)));
+        assert!(test::take_calls().contains(&Call::SetCameraPosition {
+            x: 0.0,
+            y: 1.7,
+            z: -1.0
+        }));
+
+        controller.yaw = -core::f32::consts::FRAC_PI_2;
+        test::set_keys_pressed(KeysSet(KeysSet::D));
+        controller.update(0.5).unwrap();
+        // facing +X, right is +Z
+        assert!(approx_eq(controller.position, Vec3::new(0.0, 1.7, 0.0)));
+        test::set_keys_pressed(KeysSet(0));
+    }
+
+    #[test]
+    fn orbit_zoom_is_clamped() {
+        let mut orbit = OrbitController::new(Vec3::ZERO, 5.0, 2.0, 10.0);
+        test::set_scroll_delta(-100_000.0);
+        orbit.update(0.016).unwrap();
+        assert_eq!(orbit.distance, 2.0);
+
+        test::set_scroll_delta(100_000.0);
+        orbit.update(0.016).unwrap();
+        assert_eq!(orbit.distance, 10.0);
+        assert!(approx_eq(orbit.position(), Vec3::new(0.0, 0.0, 10.0)));
+
+        // the scroll is consumed, so the next frame keeps the distance
+        orbit.update(0.016).unwrap();
+        assert_eq!(orbit.distance, 10.0);
+    }
+
+    #[test]
+    fn orbit_rotates_only_while_dragging() {
+        let mut orbit = OrbitController::new(Vec3::new(1.0, 0.0, 0.0), 3.0, 1.0, 10.0);
+        test::set_mouse_movement(100, 0);
+        orbit.update(0.016).unwrap();
+        assert_eq!(orbit.yaw, 0.0);
+
+        test::set_mouse_buttons(MouseButtons(MouseButtons::LEFT));
+        orbit.sensitivity = core::f32::consts::PI / 1000.0;
+        test::set_mouse_movement(-1000, 0);
+        test::take_calls();
+        orbit.update(0.016).unwrap();
+        test::set_mouse_buttons(MouseButtons(0));
+        assert!(approx_eq(orbit.position(), Vec3::new(1.0, 0.0, -3.0)));
+        assert!(test::take_calls().contains(&Call::CameraLookAt {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0
+        }));
+    }
+}
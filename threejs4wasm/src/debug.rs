@@ -0,0 +1,184 @@
+//! Immediate-mode debug drawing: shapes queued during a frame are drawn by [`flush`] and
+//! replaced by the next flush, so they only have to be queued where they are computed.
+//!
+//! ```ignore
+//! ctx::run_loop(move |delta| {
+//!     debug::debug_box(&player_box, Color::GREEN);
+//!     debug::debug_line(player, target, Color::RED);
+//!     debug::flush().is_ok()
+//! })?;
+//! ```
+//!
+//! Every shape becomes its own line object, created and disposed again on every frame. This
+//! is cheap for tens of shapes, not thousands; use [`ctx::create_line`] and update it in place
+//! for anything bigger.
+
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use crate::{Aabb, Color, MaterialClass, ObjectId, ThreeError, Vec3, ctx};
+
+/// Half the length of the arms of the cross drawn by [`debug_point`].
+pub const POINT_SIZE: f32 = 0.1;
+
+struct DebugState {
+    /// Points of all queued shapes, back to back.
+    points: Vec<Vec3>,
+    /// End index into `points` and color of each queued shape.
+    shapes: Vec<(usize, Color)>,
+    /// Objects created by the last flush.
+    drawn: Vec<ObjectId>,
+}
+
+impl DebugState {
+    const fn new() -> Self {
+        DebugState {
+            points: Vec::new(),
+            shapes: Vec::new(),
+            drawn: Vec::new(),
+        }
+    }
+}
+
+// the host recorder is per thread, so the queue has to be as well for parallel tests
+#[cfg(not(target_arch = "wasm32"))]
+std::thread_local! {
+    static STATE: RefCell<DebugState> = const { RefCell::new(DebugState::new()) };
+}
+
+#[cfg(target_arch = "wasm32")]
+struct SingleThreaded(RefCell<DebugState>);
+
+// SAFETY: wasm32-unknown-unknown runs the module on a single thread
+#[cfg(target_arch = "wasm32")]
+unsafe impl Sync for SingleThreaded {}
+
+#[cfg(target_arch = "wasm32")]
+static STATE: SingleThreaded = SingleThreaded(RefCell::new(DebugState::new()));
+
+#[cfg(not(target_arch = "wasm32"))]
+fn with_state<R>(f: impl FnOnce(&mut DebugState) -> R) -> R {
+    STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn with_state<R>(f: impl FnOnce(&mut DebugState) -> R) -> R {
+    f(&mut STATE.0.borrow_mut())
+}
+
+fn queue(points: &[Vec3], color: Color) {
+    with_state(|state| {
+        state.points.extend_from_slice(points);
+        let end = state.points.len();
+        state.shapes.push((end, color));
+    });
+}
+
+/// Queues a line from `a` to `b`.
+pub fn debug_line(a: Vec3, b: Vec3, color: impl Into<Color>) {
+    queue(&[a, b], color.into());
+}
+
+/// Queues the 12 edges of the box.
+pub fn debug_box(aabb: &Aabb, color: impl Into<Color>) {
+    let (lo, hi) = (aabb.min, aabb.max);
+    let b = [
+        Vec3::new(lo.x, lo.y, lo.z),
+        Vec3::new(hi.x, lo.y, lo.z),
+        Vec3::new(hi.x, lo.y, hi.z),
+        Vec3::new(lo.x, lo.y, hi.z),
+    ];
+    let t = b.map(|p| Vec3::new(p.x, hi.y, p.z));
+    // a single strip: the bottom loop, then the top loop, going back down the vertical
+    // edges along the way
+    queue(
+        &[
+            b[0], b[1], b[2], b[3], b[0], t[0], t[1], b[1], t[1], t[2], b[2], t[2], t[3], b[3],
+            t[3], t[0],
+        ],
+        color.into(),
+    );
+}
+
+/// Queues a small cross (see [`POINT_SIZE`]) centered on `p`.
+pub fn debug_point(p: Vec3, color: impl Into<Color>) {
+    let (x, y, z) = (
+        Vec3::new(POINT_SIZE, 0.0, 0.0),
+        Vec3::new(0.0, POINT_SIZE, 0.0),
+        Vec3::new(0.0, 0.0, POINT_SIZE),
+    );
+    queue(
+        &[p - x, p + x, p, p - y, p + y, p, p - z, p + z],
+        color.into(),
+    );
+}
+
+/// Disposes the shapes drawn by the last flush and adds the ones queued since to the scene.
+/// Call it once per frame, before rendering; inside `ctx::run_loop` the scene is rendered
+/// automatically after the closure returns.
+///
+/// The queue is emptied even if drawing fails. Failing to dispose an old shape doesn't stop
+/// the others from being disposed or the new ones from being drawn; the first error is
+/// returned.
+pub fn flush() -> Result<(), ThreeError> {
+    let (points, shapes, drawn) = with_state(|state| {
+        (
+            core::mem::take(&mut state.points),
+            core::mem::take(&mut state.shapes),
+            core::mem::take(&mut state.drawn),
+        )
+    });
+    let mut disposed = Ok(());
+    for id in drawn {
+        if let Err(err) = ctx::dispose_object(id) {
+            disposed = disposed.and(Err(err));
+        }
+    }
+
+    let mut start = 0;
+    let mut ids = Vec::with_capacity(shapes.len());
+    let result = shapes.iter().try_for_each(|&(end, color)| {
+        let id = ctx::create_line(&points[start..end], MaterialClass::LineBasicMaterial)?;
+        ids.push(id);
+        start = end;
+        ctx::set_line_color(id, color)?;
+        ctx::add_object_to_scene(id)
+    });
+    with_state(|state| state.drawn = ids);
+    disposed.and(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    #[test]
+    fn flush_replaces_last_frame() {
+        test::set_next_id(10);
+        debug_line(Vec3::ZERO, Vec3::X, Color::RED);
+        debug_box(
+            &Aabb::new(Vec3::ZERO, Vec3::new(1.0, 1.0, 1.0)),
+            Color::GREEN,
+        );
+        debug_point(Vec3::ZERO, Color::BLUE);
+
+        test::take_calls();
+        flush().unwrap();
+        let lens: Vec<i32> = test::take_calls()
+            .iter()
+            .filter_map(|call| match call {
+                test::Call::CreateLine { len, .. } => Some(*len),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lens, [6, 48, 24]);
+
+        flush().unwrap();
+        let calls = test::take_calls();
+        assert_eq!(
+            calls,
+            [10, 11, 12].map(|object_id| test::Call::DisposeObject { object_id })
+        );
+    }
+}
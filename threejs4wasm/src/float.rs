@@ -0,0 +1,222 @@
+//! `f32` functions which live in `std` rather than `core`, so that the math types work
+//! without `std`. All of them compute in `f64` and round once, which keeps them within an
+//! ulp or so of the `std` versions.
+
+#[cfg(feature = "ffi")]
+use core::f64::consts::LN_2;
+use core::f64::consts::{FRAC_2_PI, FRAC_PI_2, PI};
+
+pub(crate) fn sqrt(x: f32) -> f32 {
+    if x.is_nan() || x < 0.0 {
+        return f32::NAN;
+    }
+    if x == 0.0 || x == f32::INFINITY {
+        return x;
+    }
+
+    sqrt_f64(x as f64) as f32
+}
+
+pub(crate) fn sin_cos(x: f32) -> (f32, f32) {
+    if !x.is_finite() {
+        return (f32::NAN, f32::NAN);
+    }
+
+    // reduce to r in [-pi/4, pi/4] and the quadrant k
+    let x = x as f64;
+    let k = (x * FRAC_2_PI + if x >= 0.0 { 0.5 } else { -0.5 }) as i64;
+    let r = x - k as f64 * FRAC_PI_2;
+    let r2 = r * r;
+
+    // Taylor series, nested
+    let sin = r
+        * (1.0
+            - r2 / 6.0
+                * (1.0
+                    - r2 / 20.0
+                        * (1.0
+                            - r2 / 42.0
+                                * (1.0 - r2 / 72.0 * (1.0 - r2 / 110.0 * (1.0 - r2 / 156.0))))));
+    let cos = 1.0
+        - r2 / 2.0
+            * (1.0
+                - r2 / 12.0
+                    * (1.0
+                        - r2 / 30.0 * (1.0 - r2 / 56.0 * (1.0 - r2 / 90.0 * (1.0 - r2 / 132.0)))));
+
+    let (sin, cos) = match k.rem_euclid(4) {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    };
+    (sin as f32, cos as f32)
+}
+
+#[cfg(feature = "ffi")]
+pub(crate) fn exp(x: f32) -> f32 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 89.0 {
+        return f32::INFINITY;
+    }
+    if x < -104.0 {
+        return 0.0;
+    }
+
+    // exp(x) = 2^k * exp(r) with r in [-ln2/2, ln2/2]
+    let x = x as f64;
+    let k = (x / LN_2 + if x >= 0.0 { 0.5 } else { -0.5 }) as i64;
+    let r = x - k as f64 * LN_2;
+
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..12 {
+        term *= r / n as f64;
+        sum += term;
+    }
+    (sum * f64::from_bits(((1023 + k) as u64) << 52)) as f32
+}
+
+pub(crate) fn acos(x: f32) -> f32 {
+    if x.is_nan() || !(-1.0..=1.0).contains(&x) {
+        return f32::NAN;
+    }
+
+    let x = x as f64;
+    atan2_f64(sqrt_f64(1.0 - x * x), x) as f32
+}
+
+pub(crate) fn asin(x: f32) -> f32 {
+    if x.is_nan() || !(-1.0..=1.0).contains(&x) {
+        return f32::NAN;
+    }
+
+    let x = x as f64;
+    atan2_f64(x, sqrt_f64(1.0 - x * x)) as f32
+}
+
+pub(crate) fn atan2(y: f32, x: f32) -> f32 {
+    if x.is_nan() || y.is_nan() {
+        return f32::NAN;
+    }
+    atan2_f64(y as f64, x as f64) as f32
+}
+
+fn atan2_f64(y: f64, x: f64) -> f64 {
+    if x == 0.0 && y == 0.0 {
+        return 0.0;
+    }
+    if x.abs() >= y.abs() {
+        let a = atan_f64(y / x);
+        match (x > 0.0, y >= 0.0) {
+            (true, _) => a,
+            (false, true) => a + PI,
+            (false, false) => a - PI,
+        }
+    } else if y > 0.0 {
+        FRAC_PI_2 - atan_f64(x / y)
+    } else {
+        -FRAC_PI_2 - atan_f64(x / y)
+    }
+}
+
+fn sqrt_f64(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    // halving the exponent bits gives a guess within a few percent, Newton's method does the rest
+    let mut r = f64::from_bits((x.to_bits() >> 1) + (1023 << 51));
+    for _ in 0..6 {
+        r = 0.5 * (r + x / r);
+    }
+    r
+}
+
+/// `atan` for `|t| <= 1`.
+fn atan_f64(t: f64) -> f64 {
+    // atan(t) = 2 * atan(t / (1 + sqrt(1 + t^2))), applied twice brings |t| below 0.2
+    let t = t / (1.0 + sqrt_f64(1.0 + t * t));
+    let t = t / (1.0 + sqrt_f64(1.0 + t * t));
+    let t2 = t * t;
+    let mut term = t;
+    let mut sum = t;
+    for n in 1..12 {
+        term *= -t2;
+        sum += term / (2 * n + 1) as f64;
+    }
+    4.0 * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqrt_matches_std() {
+        for x in [0.0f32, 1e-30, 0.25, 1.0, 2.0, 3.0, 1234.5, 1e30, f32::MAX] {
+            let expected = x.sqrt();
+            assert!((sqrt(x) - expected).abs() <= expected * 1e-6, "sqrt({x})");
+        }
+        assert!(sqrt(-1.0).is_nan());
+        assert_eq!(sqrt(f32::INFINITY), f32::INFINITY);
+    }
+
+    #[test]
+    fn sin_cos_matches_std() {
+        let mut x = -20.0f32;
+        while x < 20.0 {
+            let (s, c) = sin_cos(x);
+            let (expected_s, expected_c) = x.sin_cos();
+            assert!((s - expected_s).abs() < 1e-6, "sin({x})");
+            assert!((c - expected_c).abs() < 1e-6, "cos({x})");
+            x += 0.1;
+        }
+        assert!(sin_cos(f32::NAN).0.is_nan());
+    }
+
+    #[test]
+    #[cfg(feature = "ffi")]
+    fn exp_matches_std() {
+        let mut x = -30.0f32;
+        while x < 30.0 {
+            let expected = x.exp();
+            assert!((exp(x) - expected).abs() <= expected * 1e-6, "exp({x})");
+            x += 0.37;
+        }
+        assert_eq!(exp(0.0), 1.0);
+        assert_eq!(exp(-200.0), 0.0);
+        assert_eq!(exp(200.0), f32::INFINITY);
+    }
+
+    #[test]
+    fn acos_matches_std() {
+        let mut x = -1.0f32;
+        while x <= 1.0 {
+            assert!((acos(x) - x.acos()).abs() < 1e-6, "acos({x})");
+            x += 0.01;
+        }
+        assert_eq!(acos(1.0), 0.0);
+        assert!(acos(1.5).is_nan());
+    }
+
+    #[test]
+    fn asin_and_atan2_match_std() {
+        let mut x = -1.0f32;
+        while x <= 1.0 {
+            assert!((asin(x) - x.asin()).abs() < 1e-6, "asin({x})");
+            x += 0.01;
+        }
+        for (y, x) in [
+            (1.0f32, 0.0f32),
+            (-1.0, 0.0),
+            (0.5, -1.0),
+            (-0.5, -1.0),
+            (-1.0, 0.5),
+            (3.0, 2.0),
+        ] {
+            assert!((atan2(y, x) - y.atan2(x)).abs() < 1e-6, "atan2({y}, {x})");
+        }
+    }
+}
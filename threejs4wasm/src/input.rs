@@ -0,0 +1,252 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::{Gamepad, GamepadButton};
+use crate::{Key, KeysSet, Vec3};
+
+/// Which way WSAD move, for [`KeysSet::movement_vector_with`].
+///
+/// The default has W going forward along -Z, where a three.js camera looks, and D going
+/// right along +X.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementMapping {
+    /// Direction of W. S moves the opposite way.
+    pub forward: Vec3,
+    /// Direction of D. A moves the opposite way.
+    pub right: Vec3,
+}
+
+impl Default for MovementMapping {
+    fn default() -> Self {
+        MovementMapping {
+            forward: Vec3::new(0.0, 0.0, -1.0),
+            right: Vec3::X,
+        }
+    }
+}
+
+/// Keyboard state of the current and the previous frame, for detecting key presses and
+/// releases rather than just held keys.
+///
+/// Call [`InputState::poll`] once at the start of every frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InputState {
+    previous: KeysSet,
+    current: KeysSet,
+}
+
+impl InputState {
+    /// Reads the pressed keys from the JS side and makes them the current frame.
+    #[cfg(feature = "ffi")]
+    pub fn poll(&mut self) {
+        self.update(crate::ctx::get_keys_pressed());
+    }
+
+    /// Makes `keys` the current frame, e.g. when the keys come from somewhere else than
+    /// [`crate::ctx::get_keys_pressed`].
+    pub fn update(&mut self, keys: KeysSet) {
+        self.previous = self.current;
+        self.current = keys;
+    }
+
+    /// Whether the key went down since the previous frame.
+    pub fn just_pressed(&self, key: Key) -> bool {
+        self.current.diff(&self.previous).has(key)
+    }
+
+    /// Whether the key went up since the previous frame.
+    pub fn just_released(&self, key: Key) -> bool {
+        self.previous.diff(&self.current).has(key)
+    }
+
+    /// Whether the key is down in the current frame.
+    pub fn held(&self, key: Key) -> bool {
+        self.current.has(key)
+    }
+
+    pub fn current(&self) -> KeysSet {
+        self.current
+    }
+}
+
+/// Binds keys and gamepad buttons to the actions of a game, so that e.g. "jump" works with
+/// both Space and the South button.
+///
+/// `A` is usually a fieldless enum of the game's actions. Like [`InputState`], call
+/// [`ActionMap::poll`] once at the start of every frame, then query the actions.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionMap<A> {
+    actions: Vec<ActionState<A>>,
+    gamepad: u32,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+struct ActionState<A> {
+    action: A,
+    /// Bits of the bound keys, like [`KeysSet`].
+    keys: i32,
+    /// Bits of the bound buttons, like [`Gamepad::buttons`].
+    buttons: i32,
+    active: bool,
+    was_active: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Copy + PartialEq> ActionMap<A> {
+    /// Map without bindings, reading the gamepad at index 0.
+    pub const fn new() -> Self {
+        ActionMap {
+            actions: Vec::new(),
+            gamepad: 0,
+        }
+    }
+
+    /// Reads the gamepad at `index` instead, see [`crate::ctx::get_gamepad`].
+    pub fn with_gamepad(mut self, index: u32) -> Self {
+        self.gamepad = index;
+        self
+    }
+
+    fn state_mut(&mut self, action: A) -> &mut ActionState<A> {
+        let index = match self.actions.iter().position(|state| state.action == action) {
+            Some(index) => index,
+            None => {
+                self.actions.push(ActionState {
+                    action,
+                    keys: 0,
+                    buttons: 0,
+                    active: false,
+                    was_active: false,
+                });
+                self.actions.len() - 1
+            }
+        };
+        &mut self.actions[index]
+    }
+
+    fn state(&self, action: A) -> Option<&ActionState<A>> {
+        self.actions.iter().find(|state| state.action == action)
+    }
+
+    /// Makes `key` trigger `action`, in addition to its other bindings.
+    pub fn bind_key(&mut self, action: A, key: Key) -> &mut Self {
+        self.state_mut(action).keys |= key.bit();
+        self
+    }
+
+    /// Makes `button` trigger `action`, in addition to its other bindings.
+    pub fn bind_button(&mut self, action: A, button: GamepadButton) -> &mut Self {
+        self.state_mut(action).buttons |= 1 << button as i32;
+        self
+    }
+
+    /// Reads the keyboard and the gamepad from the JS side and makes them the current frame.
+    #[cfg(feature = "ffi")]
+    pub fn poll(&mut self) {
+        self.update(
+            crate::ctx::get_keys_pressed(),
+            crate::ctx::get_gamepad(self.gamepad),
+        );
+    }
+
+    /// Makes `keys` and `gamepad` (`None` if disconnected) the current frame.
+    pub fn update(&mut self, keys: KeysSet, gamepad: Option<Gamepad>) {
+        let buttons = gamepad.map_or(0, |gamepad| gamepad.buttons);
+        for state in &mut self.actions {
+            state.was_active = state.active;
+            state.active = (keys.0 & state.keys) != 0 || (buttons & state.buttons) != 0;
+        }
+    }
+
+    /// Whether any binding of the action is held in the current frame.
+    pub fn is_active(&self, action: A) -> bool {
+        self.state(action).is_some_and(|state| state.active)
+    }
+
+    /// Whether the action became active since the previous frame. Pressing a second binding
+    /// while the first one is held does not activate it again.
+    pub fn just_activated(&self, action: A) -> bool {
+        self.state(action)
+            .is_some_and(|state| state.active && !state.was_active)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Copy + PartialEq> Default for ActionMap<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "ffi"))]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    #[test]
+    fn poll_detects_edges() {
+        let mut input = InputState::default();
+
+        test::set_keys_pressed(KeysSet(KeysSet::A));
+        input.poll();
+        assert!(input.just_pressed(Key::A));
+        assert!(input.held(Key::A));
+        assert!(!input.just_released(Key::A));
+
+        test::set_keys_pressed(KeysSet(KeysSet::W));
+        input.poll();
+        assert!(!input.just_pressed(Key::A));
+        assert!(!input.held(Key::A));
+        assert!(input.just_released(Key::A));
+        assert!(input.just_pressed(Key::W));
+
+        input.poll();
+        assert!(!input.just_pressed(Key::W));
+        assert!(input.held(Key::W));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Action {
+        Jump,
+        Fire,
+    }
+
+    #[test]
+    fn keyboard_and_gamepad_trigger_the_same_action() {
+        let mut actions = ActionMap::new();
+        actions
+            .bind_key(Action::Jump, Key::Space)
+            .bind_button(Action::Jump, GamepadButton::South)
+            .bind_key(Action::Fire, Key::Ctrl);
+
+        test::set_keys_pressed(KeysSet(KeysSet::SPACE));
+        actions.poll();
+        assert!(actions.just_activated(Action::Jump));
+        assert!(!actions.is_active(Action::Fire));
+
+        test::set_keys_pressed(KeysSet(0));
+        actions.poll();
+        assert!(!actions.is_active(Action::Jump));
+
+        test::set_gamepad(Some(Gamepad {
+            buttons: 1 << GamepadButton::South as i32,
+            axes: [0.0; 4],
+        }));
+        actions.poll();
+        assert!(actions.just_activated(Action::Jump));
+
+        // holding both devices keeps the action active without activating it again
+        test::set_keys_pressed(KeysSet(KeysSet::SPACE));
+        actions.poll();
+        assert!(actions.is_active(Action::Jump));
+        assert!(!actions.just_activated(Action::Jump));
+
+        test::set_gamepad(None);
+        test::set_keys_pressed(KeysSet(0));
+        actions.poll();
+        assert!(!actions.is_active(Action::Jump));
+    }
+}
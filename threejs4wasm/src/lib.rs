@@ -1,6 +1,104 @@
+//! Rust side of the three.js bindings.
+//!
+//! The crate is `no_std`. The JS bindings ([`ctx`], [`pool`] and the `extern` block behind them)
+//! are gated behind the `ffi` feature, which is on by default. Without it the crate only
+//! contains plain data types ([`math`], [`Color`], [`KeysSet`], ...) which can be shared with
+//! logic crates that never link the JS side. Types which allocate, like [`registry`], need the
+//! `alloc` feature, which `ffi` turns on. Off wasm32 the bindings are replaced
+//! by the recorder in [`test`], which uses `std`.
+#![no_std]
 #![allow(unused_unsafe)]
 #![allow(non_snake_case)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(test, all(feature = "ffi", not(target_arch = "wasm32"))))]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+pub mod animation;
+#[cfg(feature = "ffi")]
+pub mod builder;
+#[cfg(feature = "ffi")]
+pub mod camera;
+pub mod collision;
+pub mod color;
+#[cfg(feature = "ffi")]
+pub mod controllers;
+#[cfg(feature = "ffi")]
+pub mod debug;
+mod float;
+pub mod input;
+pub mod math;
+#[cfg(feature = "ffi")]
+pub mod pool;
+#[cfg(feature = "alloc")]
+pub mod registry;
+pub mod rng;
+pub mod sprite;
+pub mod timestep;
+pub mod tween;
+
+#[cfg(feature = "alloc")]
+pub use animation::{Track, TransformTrack};
+#[cfg(feature = "ffi")]
+pub use builder::ObjectBuilder;
+#[cfg(feature = "ffi")]
+pub use camera::FollowCamera;
+#[cfg(feature = "alloc")]
+pub use collision::SpatialHash;
+pub use color::Color;
+#[cfg(feature = "ffi")]
+pub use controllers::{FirstPersonController, OrbitController};
+#[cfg(feature = "alloc")]
+pub use input::ActionMap;
+pub use input::{InputState, MovementMapping};
+pub use math::{Aabb, CoordinateSystem, Euler, Mat4, Quat, RotationOrder, Transform, Vec3};
+#[cfg(feature = "ffi")]
+pub use pool::ObjectPool;
+#[cfg(feature = "alloc")]
+pub use registry::ObjectRegistry;
+pub use rng::Rng;
+pub use sprite::{Atlas, Rect, SpriteSheet};
+pub use timestep::{FixedLoop, SmoothedTransform};
+pub use tween::{Easing, Tween};
+
+/// Enums of three.js classes ([`MaterialClass`], [`GeometryClass`], [`LightClass`]) whose
+/// discriminants are the ids understood by the JS side. Each enum uses its own range, so
+/// `from_i32` rejects the ids of the others.
+pub trait ClassId: Sized + Copy + 'static {
+    /// Every variant, in declaration order.
+    const ALL: &'static [Self];
+
+    fn as_i32(&self) -> i32;
+
+    /// The variant with the given id, or `None` if the id belongs to no variant.
+    fn from_i32(value: i32) -> Option<Self>;
+}
+
+/// Implements [`ClassId`] for a fieldless `#[repr(i32)]` enum. The `match` in `as_i32`
+/// makes forgetting a variant a compile error.
+macro_rules! class_id {
+    ($ty:ident { $($variant:ident),* $(,)? }) => {
+        impl ClassId for $ty {
+            const ALL: &'static [Self] = &[$($ty::$variant),*];
+
+            fn as_i32(&self) -> i32 {
+                match self {
+                    $($ty::$variant => $ty::$variant as i32,)*
+                }
+            }
+
+            fn from_i32(value: i32) -> Option<Self> {
+                Self::ALL.iter().copied().find(|class| class.as_i32() == value)
+            }
+        }
+    };
+}
+
+/// Material classes understood by `createObject`, with the same contract as
+/// [`GeometryClass`]: names match three.js and discriminants the `MaterialClass` enum in
+/// `index.ts`, using the 10xx range.
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MaterialClass {
@@ -15,8 +113,45 @@ pub enum MaterialClass {
     LineBasicMaterial = 1010,
     LineDashedMaterial = 1011,
     PointsMaterial = 1012,
+    /// Unlit shading read from a "material capture" texture, set with
+    /// `ctx::set_matcap_texture`. Respects the color, opacity, map, normal map and flat
+    /// shading setters, ignores lights.
+    MeshMatcapMaterial = 1013,
+    /// Draws the distance to a point light, three.js uses it for point light shadows. Only
+    /// the visibility and depth setters have an effect.
+    MeshDistanceMaterial = 1014,
+    /// Invisible except for the shadows it receives, e.g. for a ground under a model on a
+    /// plain background. Respects color and opacity; enable `ctx::set_receive_shadow`.
+    ShadowMaterial = 1015,
+    /// Material of sprites, which always face the camera. Only valid through
+    /// `ctx::create_sprite`; respects color, opacity, map and alpha test.
+    SpriteMaterial = 1016,
 }
 
+class_id!(MaterialClass {
+    MeshBasicMaterial,
+    MeshLambertMaterial,
+    MeshPhongMaterial,
+    MeshStandardMaterial,
+    MeshPhysicalMaterial,
+    MeshToonMaterial,
+    MeshDepthMaterial,
+    MeshNormalMaterial,
+    LineBasicMaterial,
+    LineDashedMaterial,
+    PointsMaterial,
+    MeshMatcapMaterial,
+    MeshDistanceMaterial,
+    ShadowMaterial,
+    SpriteMaterial,
+});
+
+/// Geometry classes understood by `createObject`.
+///
+/// The JS side resolves the variant name to the three.js class of the same name
+/// (`THREE[GeometryClass[id]]`), so names must match three.js exactly and the
+/// discriminants must match the `GeometryClass` enum in `index.ts`. New variants
+/// get the next free value in the 2xxx range; existing values never change.
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GeometryClass {
@@ -26,6 +161,328 @@ pub enum GeometryClass {
     CylinderGeometry = 2004,
     ConeGeometry = 2005,
     TorusGeometry = 2006,
+    TetrahedronGeometry = 2007,
+    IcosahedronGeometry = 2008,
+    OctahedronGeometry = 2009,
+    DodecahedronGeometry = 2010,
+    RingGeometry = 2011,
+    TorusKnotGeometry = 2012,
+    CircleGeometry = 2013,
+}
+
+class_id!(GeometryClass {
+    BoxGeometry,
+    SphereGeometry,
+    PlaneGeometry,
+    CylinderGeometry,
+    ConeGeometry,
+    TorusGeometry,
+    TetrahedronGeometry,
+    IcosahedronGeometry,
+    OctahedronGeometry,
+    DodecahedronGeometry,
+    RingGeometry,
+    TorusKnotGeometry,
+    CircleGeometry,
+});
+
+/// Whether `material` can be used on a mesh built from `geometry`.
+///
+/// Every [`GeometryClass`] is a mesh geometry, so the valid combinations are:
+///
+/// | material                                  | geometry |
+/// |-------------------------------------------|----------|
+/// | `Mesh*Material`                           | any      |
+/// | `LineBasicMaterial`, `LineDashedMaterial` | none, use `ctx::create_line` |
+/// | `PointsMaterial`                          | none, use `ctx::create_points` |
+/// | `SpriteMaterial`                          | none, use `ctx::create_sprite` |
+pub const fn is_valid_combo(geometry: GeometryClass, material: MaterialClass) -> bool {
+    let _ = geometry;
+    !matches!(
+        material,
+        MaterialClass::LineBasicMaterial
+            | MaterialClass::LineDashedMaterial
+            | MaterialClass::PointsMaterial
+            | MaterialClass::SpriteMaterial
+    )
+}
+
+/// Maximum number of floats any [`GeometryParams`] variant serializes to.
+pub const GEOMETRY_PARAMS_MAX_LEN: usize = 6;
+
+/// Constructor parameters for each [`GeometryClass`].
+///
+/// Fields are passed positionally to the three.js constructor, in the same order
+/// as the three.js docs. A field left as `None` is sent as `NaN`, which the JS side
+/// turns into `undefined` so that three.js applies its own default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeometryParams {
+    Box {
+        width: Option<f32>,
+        height: Option<f32>,
+        depth: Option<f32>,
+    },
+    Sphere {
+        radius: Option<f32>,
+        width_segments: Option<u32>,
+        height_segments: Option<u32>,
+    },
+    Plane {
+        width: Option<f32>,
+        height: Option<f32>,
+        width_segments: Option<u32>,
+        height_segments: Option<u32>,
+    },
+    Cylinder {
+        radius_top: Option<f32>,
+        radius_bottom: Option<f32>,
+        height: Option<f32>,
+        radial_segments: Option<u32>,
+    },
+    Cone {
+        radius: Option<f32>,
+        height: Option<f32>,
+        radial_segments: Option<u32>,
+    },
+    Torus {
+        radius: Option<f32>,
+        tube: Option<f32>,
+        radial_segments: Option<u32>,
+        tubular_segments: Option<u32>,
+    },
+    Tetrahedron {
+        radius: Option<f32>,
+        detail: Option<u32>,
+    },
+    Icosahedron {
+        radius: Option<f32>,
+        detail: Option<u32>,
+    },
+    Octahedron {
+        radius: Option<f32>,
+        detail: Option<u32>,
+    },
+    Dodecahedron {
+        radius: Option<f32>,
+        detail: Option<u32>,
+    },
+    Ring {
+        inner_radius: Option<f32>,
+        outer_radius: Option<f32>,
+        theta_segments: Option<u32>,
+    },
+    TorusKnot {
+        radius: Option<f32>,
+        tube: Option<f32>,
+        tubular_segments: Option<u32>,
+        radial_segments: Option<u32>,
+        p: Option<u32>,
+        q: Option<u32>,
+    },
+    Circle {
+        radius: Option<f32>,
+        segments: Option<u32>,
+    },
+}
+
+#[inline(always)]
+fn param_f(value: Option<f32>) -> f32 {
+    value.unwrap_or(f32::NAN)
+}
+
+#[inline(always)]
+fn param_u(value: Option<u32>) -> f32 {
+    value.map_or(f32::NAN, |v| v as f32)
+}
+
+fn pack_params<const N: usize>(values: [f32; N]) -> ([f32; GEOMETRY_PARAMS_MAX_LEN], usize) {
+    let mut buf = [f32::NAN; GEOMETRY_PARAMS_MAX_LEN];
+    buf[..N].copy_from_slice(&values);
+    (buf, N)
+}
+
+impl GeometryParams {
+    pub fn geometry_class(&self) -> GeometryClass {
+        match self {
+            GeometryParams::Box { .. } => GeometryClass::BoxGeometry,
+            GeometryParams::Sphere { .. } => GeometryClass::SphereGeometry,
+            GeometryParams::Plane { .. } => GeometryClass::PlaneGeometry,
+            GeometryParams::Cylinder { .. } => GeometryClass::CylinderGeometry,
+            GeometryParams::Cone { .. } => GeometryClass::ConeGeometry,
+            GeometryParams::Torus { .. } => GeometryClass::TorusGeometry,
+            GeometryParams::Tetrahedron { .. } => GeometryClass::TetrahedronGeometry,
+            GeometryParams::Icosahedron { .. } => GeometryClass::IcosahedronGeometry,
+            GeometryParams::Octahedron { .. } => GeometryClass::OctahedronGeometry,
+            GeometryParams::Dodecahedron { .. } => GeometryClass::DodecahedronGeometry,
+            GeometryParams::Ring { .. } => GeometryClass::RingGeometry,
+            GeometryParams::TorusKnot { .. } => GeometryClass::TorusKnotGeometry,
+            GeometryParams::Circle { .. } => GeometryClass::CircleGeometry,
+        }
+    }
+
+    /// Serializes the parameters into a fixed buffer, returning it with the number of used floats.
+    pub fn to_buffer(&self) -> ([f32; GEOMETRY_PARAMS_MAX_LEN], usize) {
+        match *self {
+            GeometryParams::Box {
+                width,
+                height,
+                depth,
+            } => pack_params([param_f(width), param_f(height), param_f(depth)]),
+            GeometryParams::Sphere {
+                radius,
+                width_segments,
+                height_segments,
+            } => pack_params([
+                param_f(radius),
+                param_u(width_segments),
+                param_u(height_segments),
+            ]),
+            GeometryParams::Plane {
+                width,
+                height,
+                width_segments,
+                height_segments,
+            } => pack_params([
+                param_f(width),
+                param_f(height),
+                param_u(width_segments),
+                param_u(height_segments),
+            ]),
+            GeometryParams::Cylinder {
+                radius_top,
+                radius_bottom,
+                height,
+                radial_segments,
+            } => pack_params([
+                param_f(radius_top),
+                param_f(radius_bottom),
+                param_f(height),
+                param_u(radial_segments),
+            ]),
+            GeometryParams::Cone {
+                radius,
+                height,
+                radial_segments,
+            } => pack_params([param_f(radius), param_f(height), param_u(radial_segments)]),
+            GeometryParams::Torus {
+                radius,
+                tube,
+                radial_segments,
+                tubular_segments,
+            } => pack_params([
+                param_f(radius),
+                param_f(tube),
+                param_u(radial_segments),
+                param_u(tubular_segments),
+            ]),
+            GeometryParams::Tetrahedron { radius, detail }
+            | GeometryParams::Icosahedron { radius, detail }
+            | GeometryParams::Octahedron { radius, detail }
+            | GeometryParams::Dodecahedron { radius, detail } => {
+                pack_params([param_f(radius), param_u(detail)])
+            }
+            GeometryParams::Ring {
+                inner_radius,
+                outer_radius,
+                theta_segments,
+            } => pack_params([
+                param_f(inner_radius),
+                param_f(outer_radius),
+                param_u(theta_segments),
+            ]),
+            GeometryParams::TorusKnot {
+                radius,
+                tube,
+                tubular_segments,
+                radial_segments,
+                p,
+                q,
+            } => pack_params([
+                param_f(radius),
+                param_f(tube),
+                param_u(tubular_segments),
+                param_u(radial_segments),
+                param_u(p),
+                param_u(q),
+            ]),
+            GeometryParams::Circle { radius, segments } => {
+                pack_params([param_f(radius), param_u(segments)])
+            }
+        }
+    }
+}
+
+/// Light classes understood by `createLight`.
+///
+/// Same contract as [`GeometryClass`]: names match the three.js classes and the
+/// discriminants match the `LightClass` enum in `index.ts`.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightClass {
+    AmbientLight = 3001,
+    DirectionalLight = 3002,
+    PointLight = 3003,
+    SpotLight = 3004,
+    HemisphereLight = 3005,
+}
+
+class_id!(LightClass {
+    AmbientLight,
+    DirectionalLight,
+    PointLight,
+    SpotLight,
+    HemisphereLight,
+});
+
+/// Which faces of a mesh are rendered, see [`ctx::set_material_side`].
+///
+/// The discriminants are the values of the three.js `FrontSide`, `BackSide` and `DoubleSide`
+/// constants.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Side {
+    #[default]
+    Front = 0,
+    Back = 1,
+    Double = 2,
+}
+
+/// Tone mapping applied by the renderer, see [`ctx::set_tone_mapping`].
+///
+/// The discriminants are the values of the matching three.js constants (`NoToneMapping`,
+/// `LinearToneMapping`, ...).
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToneMapping {
+    #[default]
+    None = 0,
+    Linear = 1,
+    Reinhard = 2,
+    Cineon = 3,
+    AcesFilmic = 4,
+    AgX = 6,
+    Neutral = 7,
+}
+
+/// How texture coordinates outside `0..1` are handled, see [`ctx::set_texture_wrap`].
+///
+/// The discriminants are the values of the matching three.js constants:
+///
+/// | variant          | three.js                 |
+/// |------------------|--------------------------|
+/// | `Repeat`         | `RepeatWrapping`         |
+/// | `ClampToEdge`    | `ClampToEdgeWrapping`    |
+/// | `MirroredRepeat` | `MirroredRepeatWrapping` |
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Wrap {
+    /// Tiles the texture.
+    Repeat = 1000,
+    /// Stretches the edge pixels, the three.js default.
+    #[default]
+    ClampToEdge = 1001,
+    /// Tiles the texture, flipping every other copy.
+    MirroredRepeat = 1002,
 }
 
 #[repr(C)]
@@ -43,10 +500,167 @@ impl From<i32> for TwoI16 {
     }
 }
 
+/// Handle to an object stored on the JS side (mesh, sprite, ...).
+///
+/// Ids can only be obtained from the `ctx` constructors, so it is not possible to
+/// accidentally pass an arbitrary integer where an object is expected.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObjectId(i32);
+
+impl ObjectId {
+    #[cfg(any(feature = "ffi", all(test, feature = "alloc")))]
+    #[inline(always)]
+    pub(crate) fn new(raw: i32) -> Self {
+        ObjectId(raw)
+    }
+
+    /// Returns the raw id as understood by the JS side.
+    #[inline(always)]
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+}
+
+/// Handle to a texture stored on the JS side (loaded image, cube map or render target).
+///
+/// Like [`ObjectId`] it can only be obtained from the `ctx` constructors, so a texture can't
+/// be passed where an object is expected or the other way round.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextureHandle(i32);
+
+impl TextureHandle {
+    #[cfg(feature = "ffi")]
+    #[inline(always)]
+    pub(crate) fn new(raw: i32) -> Self {
+        TextureHandle(raw)
+    }
+
+    /// Returns the raw id as understood by the JS side.
+    #[inline(always)]
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+}
+
+/// Handle to a camera stored on the JS side, either [`CameraId::DEFAULT`] or one made by
+/// [`ctx::create_camera`].
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CameraId(i32);
+
+impl CameraId {
+    /// The camera created by `init`, active until another one is activated.
+    pub const DEFAULT: CameraId = CameraId(0);
+
+    #[cfg(feature = "ffi")]
+    #[inline(always)]
+    pub(crate) fn new(raw: i32) -> Self {
+        CameraId(raw)
+    }
+
+    /// Returns the raw id as understood by the JS side.
+    #[inline(always)]
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+}
+
+/// Error returned by the `ctx` wrappers when the JS side reports a failure.
+///
+/// The JS bindings return a non-negative value on success and one of the
+/// negative codes below on failure (see `ErrorCode` in `index.ts`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreeError {
+    /// No object with the given id exists (code `-1`).
+    UnknownObject,
+    /// The object exists but the operation does not apply to it, or an argument is invalid (code `-2`).
+    InvalidArgument,
+    /// three.js threw an exception while handling the call (code `-3`).
+    JsException,
+    /// The renderer, scene or camera was used before `init` (code `-4`).
+    NotInitialized,
+    /// Any other negative code.
+    Unknown(i32),
+}
+
+impl ThreeError {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            -1 => ThreeError::UnknownObject,
+            -2 => ThreeError::InvalidArgument,
+            -3 => ThreeError::JsException,
+            -4 => ThreeError::NotInitialized,
+            other => ThreeError::Unknown(other),
+        }
+    }
+}
+
+impl core::fmt::Display for ThreeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ThreeError::UnknownObject => write!(f, "unknown object"),
+            ThreeError::InvalidArgument => write!(f, "invalid argument"),
+            ThreeError::JsException => write!(f, "JS exception"),
+            ThreeError::NotInitialized => write!(f, "context not initialized"),
+            ThreeError::Unknown(code) => write!(f, "unknown error (code {code})"),
+        }
+    }
+}
+
+impl core::error::Error for ThreeError {}
+
+/// Interprets the return value of a JS binding which returns a status code.
+#[cfg(feature = "ffi")]
+#[inline(always)]
+pub(crate) fn check(code: i32) -> Result<(), ThreeError> {
+    if code < 0 {
+        Err(ThreeError::from_code(code))
+    } else {
+        Ok(())
+    }
+}
+
+/// Interprets the return value of a JS binding which returns a newly created object id.
+#[cfg(feature = "ffi")]
+#[inline(always)]
+pub(crate) fn check_id(code: i32) -> Result<ObjectId, ThreeError> {
+    if code < 0 {
+        Err(ThreeError::from_code(code))
+    } else {
+        Ok(ObjectId::new(code))
+    }
+}
+
+/// Converts a boolean into the 0/1 flag expected by the JS side.
+#[cfg(feature = "ffi")]
+#[inline(always)]
+pub(crate) fn flag(enabled: bool) -> i32 {
+    enabled as i32
+}
+
+/// Interprets the return value of a JS binding which returns a non-negative value on success.
+#[cfg(feature = "ffi")]
+#[inline(always)]
+pub(crate) fn check_value(code: i32) -> Result<i32, ThreeError> {
+    if code < 0 {
+        Err(ThreeError::from_code(code))
+    } else {
+        Ok(code)
+    }
+}
+
 // External JavaScript functions provided in your JS runtime environment
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(feature = "ffi", target_arch = "wasm32"))]
 unsafe extern "C" {
     pub fn createObject(geometry: GeometryClass, material: MaterialClass) -> i32;
+    pub fn createObjectParameterized(
+        geometry: GeometryClass,
+        material: MaterialClass,
+        params_ptr: *const f32,
+        params_len: i32,
+    ) -> i32;
     pub fn createSprite(texture_id: i32) -> i32;
     pub fn setPosition(object_id: i32, x: f32, y: f32, z: f32) -> i32;
     pub fn setRotation(object_id: i32, x: f32, y: f32, z: f32) -> i32;
@@ -60,58 +674,585 @@ unsafe extern "C" {
     pub fn getKeysPressed() -> i32;
     pub fn getMouseMovement() -> i32;
     pub fn render() -> i32;
+    pub fn createLight(light: LightClass, color: i32, intensity: f32) -> i32;
+    pub fn setLightParams(light_id: i32, distance: f32, decay: f32) -> i32;
+    pub fn setLightTarget(light_id: i32, x: f32, y: f32, z: f32) -> i32;
+    pub fn setMaterialColor(object_id: i32, color: i32) -> i32;
+    pub fn setMaterialOpacity(object_id: i32, opacity: f32, transparent: i32) -> i32;
+    pub fn setMaterialPBR(object_id: i32, metalness: f32, roughness: f32) -> i32;
+    pub fn setMaterialEmissive(object_id: i32, color: i32, intensity: f32) -> i32;
+    pub fn setWireframe(object_id: i32, enabled: i32) -> i32;
+    pub fn getMouseButtons() -> i32;
+    pub fn getMousePosition() -> i32;
+    pub fn getDeltaTime() -> f32;
+    pub fn getElapsedTime() -> f32;
+    pub fn setCameraFov(fov: f32) -> i32;
+    pub fn setCameraAspect(aspect: f32) -> i32;
+    pub fn setCameraClip(near: f32, far: f32) -> i32;
+    pub fn useOrthographicCamera(
+        left: f32,
+        right: f32,
+        top: f32,
+        bottom: f32,
+        near: f32,
+        far: f32,
+    ) -> i32;
+    pub fn usePerspectiveCamera(fov: f32, aspect: f32, near: f32, far: f32) -> i32;
+    pub fn addChild(parent_id: i32, child_id: i32) -> i32;
+    pub fn removeChild(parent_id: i32, child_id: i32) -> i32;
+    pub fn createGroup() -> i32;
+    pub fn loadTexture(url_ptr: *const u8, url_len: i32) -> i32;
+    pub fn textureIsReady(texture_id: i32) -> i32;
+    pub fn setMaterialMap(object_id: i32, texture_id: i32) -> i32;
+    pub fn setMaterialNormalMap(object_id: i32, texture_id: i32) -> i32;
+    pub fn setMaterialRoughnessMap(object_id: i32, texture_id: i32) -> i32;
+    pub fn disposeObject(object_id: i32) -> i32;
+    pub fn setQuaternion(object_id: i32, x: f32, y: f32, z: f32, w: f32) -> i32;
+    pub fn setFog(color: i32, near: f32, far: f32) -> i32;
+    pub fn setFogExp2(color: i32, density: f32) -> i32;
+    pub fn clearFog() -> i32;
+    pub fn requestFrame(callback_ptr: *mut core::ffi::c_void) -> i32;
+    pub fn raycastFromCamera(ndc_x: f32, ndc_y: f32) -> i32;
+    pub fn raycastPoint(object_id: i32, out_ptr: *mut f32) -> i32;
+    pub fn setCastShadow(object_id: i32, enabled: i32) -> i32;
+    pub fn setReceiveShadow(object_id: i32, enabled: i32) -> i32;
+    pub fn enableShadows() -> i32;
+    pub fn setLightCastShadow(light_id: i32, enabled: i32) -> i32;
+    pub fn createInstancedMesh(geometry: GeometryClass, material: MaterialClass, count: i32)
+    -> i32;
+    pub fn setInstanceTransform(
+        mesh_id: i32,
+        index: i32,
+        px: f32,
+        py: f32,
+        pz: f32,
+        rx: f32,
+        ry: f32,
+        rz: f32,
+        sx: f32,
+        sy: f32,
+        sz: f32,
+    ) -> i32;
+    pub fn updateInstances(mesh_id: i32) -> i32;
+    pub fn setTransform(
+        object_id: i32,
+        px: f32,
+        py: f32,
+        pz: f32,
+        rx: f32,
+        ry: f32,
+        rz: f32,
+        sx: f32,
+        sy: f32,
+        sz: f32,
+    ) -> i32;
+    pub fn getGamepadState(index: i32) -> i32;
+    pub fn getGamepadAxis(index: i32, axis: i32) -> f32;
+    pub fn loadSound(url_ptr: *const u8, url_len: i32) -> i32;
+    pub fn playSound(sound_id: i32, volume: f32, loop_: i32) -> i32;
+    pub fn playSoundAt(sound_id: i32, x: f32, y: f32, z: f32, volume: f32, loop_: i32) -> i32;
+    pub fn stopSound(sound_id: i32) -> i32;
+    pub fn setMatrix(object_id: i32, ptr: *const f32) -> i32;
+    pub fn createPoints(ptr: *const f32, len: i32, material: MaterialClass) -> i32;
+    pub fn setPointsSize(object_id: i32, size: f32) -> i32;
+    pub fn createLine(ptr: *const f32, len: i32, material: MaterialClass) -> i32;
+    pub fn setLineColor(object_id: i32, color: i32) -> i32;
+    pub fn setDashParams(object_id: i32, dash_size: f32, gap_size: f32) -> i32;
+    pub fn getBoundingBox(object_id: i32, out_ptr: *mut f32) -> i32;
+    pub fn getBoundingSphere(object_id: i32, out_ptr: *mut f32) -> i32;
+    pub fn loadModel(url_ptr: *const u8, url_len: i32) -> i32;
+    pub fn modelIsReady(model_id: i32) -> i32;
+    pub fn getModelAnimationCount(model_id: i32) -> i32;
+    pub fn getModelAnimationName(model_id: i32, index: i32, out_ptr: *mut u8, out_cap: i32) -> i32;
+    pub fn playAnimation(model_id: i32, clip_index: i32, loop_: i32) -> i32;
+    pub fn stopAnimation(model_id: i32) -> i32;
+    pub fn setAnimationTime(model_id: i32, t: f32) -> i32;
+    pub fn getAnimationDuration(model_id: i32, clip_index: i32) -> f32;
+    pub fn getCanvasSize(out_ptr: *mut i32) -> i32;
+    pub fn setRendererSize(width: i32, height: i32) -> i32;
+    pub fn setRotationOrder(object_id: i32, order: i32) -> i32;
+    pub fn setVisible(object_id: i32, visible: i32) -> i32;
+    pub fn createCamera() -> i32;
+    pub fn createRenderTarget(width: i32, height: i32) -> i32;
+    pub fn renderToTarget(target_id: i32, camera_id: i32) -> i32;
+    pub fn setActiveCamera(camera_id: i32) -> i32;
+    pub fn setCameraPositionFor(camera_id: i32, x: f32, y: f32, z: f32) -> i32;
+    pub fn cameraLookAtFor(camera_id: i32, x: f32, y: f32, z: f32) -> i32;
+    pub fn setPointsSizeAttenuation(object_id: i32, enabled: i32) -> i32;
+    pub fn loadCubeMap(urls_ptr: *const u8, urls_len: i32) -> i32;
+    pub fn setSceneBackground(texture_id: i32) -> i32;
+    pub fn setSceneEnvironment(texture_id: i32) -> i32;
+    pub fn createGrid(size: f32, divisions: i32, color: i32) -> i32;
+    pub fn createAxesHelper(size: f32) -> i32;
+    pub fn setFlatShading(object_id: i32, enabled: i32) -> i32;
+    pub fn setMaterialSide(object_id: i32, side: i32) -> i32;
+    pub fn clearScene(dispose: i32) -> i32;
+    pub fn setPixelRatio(ratio: f32) -> i32;
+    pub fn setAntialias(enabled: i32) -> i32;
+    pub fn setToneMapping(mode: i32, exposure: f32) -> i32;
+    pub fn createTextSprite(text_ptr: *const u8, text_len: i32, size: f32, color: i32) -> i32;
+    pub fn updateTextSprite(object_id: i32, text_ptr: *const u8, text_len: i32) -> i32;
+    pub fn setBillboard(object_id: i32, enabled: i32) -> i32;
+    pub fn createLine2(ptr: *const f32, len: i32, width: f32, color: i32) -> i32;
+    pub fn setDepthTest(object_id: i32, enabled: i32) -> i32;
+    pub fn setRenderOrder(object_id: i32, order: i32) -> i32;
+    pub fn cloneObject(object_id: i32) -> i32;
+    pub fn cloneObjectDeep(object_id: i32) -> i32;
+    pub fn setSSAO(enabled: i32, radius: f32, intensity: f32) -> i32;
+    pub fn setBloom(enabled: i32, strength: f32, radius: f32, threshold: f32) -> i32;
+    pub fn setAlphaTest(object_id: i32, threshold: f32) -> i32;
+    pub fn worldToScreen(x: f32, y: f32, z: f32, out_ptr: *mut f32) -> i32;
+    pub fn screenToWorld(sx: f32, sy: f32, depth: f32, out_ptr: *mut f32) -> i32;
+    pub fn loadAtlas(url_ptr: *const u8, url_len: i32) -> i32;
+    pub fn atlasIsReady(atlas_id: i32) -> i32;
+    pub fn getAtlasFrame(
+        atlas_id: i32,
+        name_ptr: *const u8,
+        name_len: i32,
+        out_ptr: *mut f32,
+    ) -> i32;
+    pub fn setSpriteFrame(object_id: i32, atlas_id: i32, name_ptr: *const u8, name_len: i32)
+    -> i32;
+    pub fn getRenderStats(out_ptr: *mut i32) -> i32;
+    pub fn disposeTexture(texture_id: i32) -> i32;
+    pub fn setVertexColors(object_id: i32, ptr: *const f32, len: i32) -> i32;
+    pub fn setVertexColorsEnabled(object_id: i32, enabled: i32) -> i32;
+    pub fn getScrollDelta() -> f32;
+    pub fn setDepthWrite(object_id: i32, enabled: i32) -> i32;
+    pub fn setMatcapTexture(object_id: i32, texture_id: i32) -> i32;
+    pub fn setMorphWeight(model_id: i32, index: i32, weight: f32) -> i32;
+    pub fn getMorphTargetCount(model_id: i32) -> i32;
+    pub fn setObjectLayer(object_id: i32, layer: i32) -> i32;
+    pub fn setCameraLayers(camera_id: i32, mask: i32) -> i32;
+    pub fn setTextureAnisotropy(texture_id: i32, level: i32) -> i32;
+    pub fn getMaxAnisotropy() -> i32;
+    pub fn setTextureWrap(texture_id: i32, wrap_s: i32, wrap_t: i32) -> i32;
+    pub fn setTextureRepeat(texture_id: i32, u: f32, v: f32) -> i32;
+    pub fn createLOD() -> i32;
+    pub fn addLODLevel(lod_id: i32, object_id: i32, distance: f32) -> i32;
+    pub fn pollEvent(out_ptr: *mut i32) -> i32;
+    pub fn setObjectOpacity(object_id: i32, opacity: f32) -> i32;
+    pub fn unshareMaterial(object_id: i32) -> i32;
+    pub fn isInFrustum(object_id: i32) -> i32;
+    pub fn setEnvMapIntensity(object_id: i32, intensity: f32) -> i32;
+    pub fn captureFrame(out_ptr: *mut u8, max_len: i32) -> i32;
+    pub fn getTypedText(out_ptr: *mut u8, max_len: i32) -> i32;
+    pub fn setImeCapture(enabled: i32) -> i32;
+    pub fn pauseLoop() -> i32;
+    pub fn resumeLoop() -> i32;
+    pub fn isPaused() -> i32;
 }
 
-mod test {
+/// Host-side stand-in for the JS bindings, used whenever the crate is not built for wasm32.
+///
+/// Every call is recorded, so tests of game logic can assert on what would have been sent
+/// to three.js:
+///
+/// ```ignore
+/// let player = ctx::create_object(GeometryClass::BoxGeometry, MaterialClass::MeshBasicMaterial)?;
+/// test::take_calls();
+/// move_left(player);
+/// assert!(matches!(test::take_calls()[..], [Call::SetPosition { x, .. }] if x < 0.0));
+/// ```
+///
+/// Calls return the same defaults as before (0, or "nothing there" for queries), except that
+/// constructors return ids counting up from 0, see [`set_next_id`]. The log and the id
+/// counter are per thread, so tests running in parallel do not see each other's calls.
+#[cfg(all(feature = "ffi", not(target_arch = "wasm32")))]
+pub mod test {
     #![allow(unused)]
 
+    use core::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+    use std::vec::Vec;
+
     use super::*;
-    // a macro which takes a function name and returns a test function
-    pub fn createObject(geometry: GeometryClass, material: MaterialClass) -> i32 {
-        0
-    }
-    pub fn createSprite(texture_id: i32) -> i32 {
-        0
+
+    std::thread_local! {
+        static CALLS: RefCell<Vec<Call>> = const { RefCell::new(Vec::new()) };
+        static NEXT_ID: Cell<i32> = const { Cell::new(0) };
+        static KEYS_PRESSED: Cell<i32> = const { Cell::new(0) };
+        static MOUSE_MOVEMENT: Cell<i32> = const { Cell::new(0) };
+        static MOUSE_BUTTONS: Cell<i32> = const { Cell::new(0) };
+        static SCROLL_DELTA: Cell<f32> = const { Cell::new(0.0) };
+        static GAMEPAD: Cell<Option<Gamepad>> = const { Cell::new(None) };
+        static EVENTS: RefCell<VecDeque<[i32; 2]>> = const { RefCell::new(VecDeque::new()) };
     }
-    pub fn setPosition(object_id: i32, x: f32, y: f32, z: f32) -> i32 {
-        0
+
+    /// Returns the calls recorded on this thread since the last call, oldest first.
+    pub fn take_calls() -> Vec<Call> {
+        CALLS.with(|calls| core::mem::take(&mut *calls.borrow_mut()))
     }
-    pub fn setRotation(object_id: i32, x: f32, y: f32, z: f32) -> i32 {
-        0
+
+    /// Sets the id returned by the next constructor. Later ones keep counting up from it.
+    pub fn set_next_id(id: i32) {
+        NEXT_ID.with(|next| next.set(id));
     }
-    pub fn setScale(object_id: i32, x: f32, y: f32, z: f32) -> i32 {
-        0
+
+    /// Sets the keys reported as pressed by `getKeysPressed` from now on.
+    pub fn set_keys_pressed(keys: KeysSet) {
+        KEYS_PRESSED.with(|pressed| pressed.set(keys.0));
     }
-    pub fn setBg(color: i32) -> i32 {
-        0
+
+    /// Sets the movement reported by the next `getMouseMovement`, which then resets it to
+    /// zero like the JS side does.
+    pub fn set_mouse_movement(x: i16, y: i16) {
+        MOUSE_MOVEMENT.with(|movement| movement.set((x as u16 as i32) | ((y as u16 as i32) << 16)));
     }
-    pub fn addObjectToScene(object_id: i32) -> i32 {
-        0
+
+    /// Sets the buttons reported as held by `getMouseButtons` from now on.
+    pub fn set_mouse_buttons(buttons: MouseButtons) {
+        MOUSE_BUTTONS.with(|held| held.set(buttons.0));
     }
-    pub fn removeObjectFromScene(object_id: i32) -> i32 {
-        0
+
+    /// Sets the gamepad reported at every index from now on, `None` meaning disconnected.
+    pub fn set_gamepad(gamepad: Option<Gamepad>) {
+        GAMEPAD.with(|connected| connected.set(gamepad));
     }
-    pub fn setSpriteAnimationOffset(object_id: i32, frame_x: i32, frame_y: i32) -> i32 {
-        0
+
+    /// Sets the scroll reported by the next `getScrollDelta`, which then resets it to zero.
+    pub fn set_scroll_delta(delta: f32) {
+        SCROLL_DELTA.with(|scroll| scroll.set(delta));
     }
-    pub fn setCameraPosition(x: f32, y: f32, z: f32) -> i32 {
-        0
+
+    /// Queues an event for `pollEvent`, after the ones queued before.
+    pub fn push_event(event: Event) {
+        let raw = match event {
+            Event::TextureLoaded(texture) => [Event::TEXTURE_LOADED, texture.raw()],
+            Event::SoundFinished(sound_id) => [Event::SOUND_FINISHED, sound_id],
+            Event::WindowBlur => [Event::WINDOW_BLUR, 0],
+            Event::WindowFocus => [Event::WINDOW_FOCUS, 0],
+        };
+        EVENTS.with(|events| events.borrow_mut().push_back(raw));
     }
-    pub fn cameraLookAt(x: f32, y: f32, z: f32) -> i32 {
-        0
+
+    fn next_id() -> i32 {
+        NEXT_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            id
+        })
     }
-    pub fn getKeysPressed() -> i32 {
-        0
+
+    fn record(call: Call) {
+        CALLS.with(|calls| calls.borrow_mut().push(call));
     }
-    pub fn getMouseMovement() -> i32 {
-        0
+
+    /// Defines [`Call`] together with one recording function per binding. A binding returns
+    /// `Default::default()` unless it is given an explicit `= value`.
+    macro_rules! recorded {
+        (@ret) => { Default::default() };
+        (@ret $value:expr) => { $value };
+        ($(
+            $(#[$attr:meta])*
+            fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty
+                => $variant:ident $(= $value:expr)?;
+        )*) => {
+            /// A recorded call into the JS bindings, with its arguments.
+            #[derive(Debug, Clone, PartialEq)]
+            pub enum Call {
+                $($variant { $($arg: $ty),* },)*
+            }
+
+            $(
+                $(#[$attr])*
+                pub fn $name($($arg: $ty),*) -> $ret {
+                    record(Call::$variant { $($arg),* });
+                    recorded!(@ret $($value)?)
+                }
+            )*
+        };
     }
-    pub fn render() -> i32 {
-        0
+
+    recorded! {
+        fn createObject(
+            geometry: GeometryClass,
+            material: MaterialClass,
+        ) -> i32 => CreateObject = next_id();
+        fn createObjectParameterized(
+            geometry: GeometryClass,
+            material: MaterialClass,
+            params_ptr: *const f32,
+            params_len: i32,
+        ) -> i32 => CreateObjectParameterized = next_id();
+        fn createSprite(texture_id: i32) -> i32 => CreateSprite = next_id();
+        fn setPosition(object_id: i32, x: f32, y: f32, z: f32) -> i32 => SetPosition;
+        fn setRotation(object_id: i32, x: f32, y: f32, z: f32) -> i32 => SetRotation;
+        fn setScale(object_id: i32, x: f32, y: f32, z: f32) -> i32 => SetScale;
+        fn setBg(color: i32) -> i32 => SetBg;
+        fn addObjectToScene(object_id: i32) -> i32 => AddObjectToScene;
+        fn removeObjectFromScene(object_id: i32) -> i32 => RemoveObjectFromScene;
+        fn setSpriteAnimationOffset(
+            object_id: i32,
+            frame_x: i32,
+            frame_y: i32,
+        ) -> i32 => SetSpriteAnimationOffset;
+        fn setCameraPosition(x: f32, y: f32, z: f32) -> i32 => SetCameraPosition;
+        fn cameraLookAt(x: f32, y: f32, z: f32) -> i32 => CameraLookAt;
+        fn getKeysPressed() -> i32 => GetKeysPressed = KEYS_PRESSED.with(Cell::get);
+        fn getMouseMovement() -> i32 => GetMouseMovement = MOUSE_MOVEMENT.with(|movement| movement.replace(0));
+        fn render() -> i32 => Render;
+        fn createLight(
+            light: LightClass,
+            color: i32,
+            intensity: f32,
+        ) -> i32 => CreateLight = next_id();
+        fn setLightParams(light_id: i32, distance: f32, decay: f32) -> i32 => SetLightParams;
+        fn setLightTarget(light_id: i32, x: f32, y: f32, z: f32) -> i32 => SetLightTarget;
+        fn setMaterialColor(object_id: i32, color: i32) -> i32 => SetMaterialColor;
+        fn setMaterialOpacity(
+            object_id: i32,
+            opacity: f32,
+            transparent: i32,
+        ) -> i32 => SetMaterialOpacity;
+        fn setMaterialPBR(object_id: i32, metalness: f32, roughness: f32) -> i32 => SetMaterialPBR;
+        fn setMaterialEmissive(
+            object_id: i32,
+            color: i32,
+            intensity: f32,
+        ) -> i32 => SetMaterialEmissive;
+        fn setWireframe(object_id: i32, enabled: i32) -> i32 => SetWireframe;
+        fn getMouseButtons() -> i32 => GetMouseButtons = MOUSE_BUTTONS.with(Cell::get);
+        fn getMousePosition() -> i32 => GetMousePosition;
+        fn getDeltaTime() -> f32 => GetDeltaTime = 0.016;
+        fn getElapsedTime() -> f32 => GetElapsedTime;
+        fn setCameraFov(fov: f32) -> i32 => SetCameraFov;
+        fn setCameraAspect(aspect: f32) -> i32 => SetCameraAspect;
+        fn setCameraClip(near: f32, far: f32) -> i32 => SetCameraClip;
+        fn useOrthographicCamera(
+            left: f32,
+            right: f32,
+            top: f32,
+            bottom: f32,
+            near: f32,
+            far: f32,
+        ) -> i32 => UseOrthographicCamera;
+        fn usePerspectiveCamera(
+            fov: f32,
+            aspect: f32,
+            near: f32,
+            far: f32,
+        ) -> i32 => UsePerspectiveCamera;
+        fn addChild(parent_id: i32, child_id: i32) -> i32 => AddChild;
+        fn removeChild(parent_id: i32, child_id: i32) -> i32 => RemoveChild;
+        fn createGroup() -> i32 => CreateGroup = next_id();
+        fn loadTexture(url_ptr: *const u8, url_len: i32) -> i32 => LoadTexture = next_id();
+        fn textureIsReady(texture_id: i32) -> i32 => TextureIsReady;
+        fn setMaterialMap(object_id: i32, texture_id: i32) -> i32 => SetMaterialMap;
+        fn setMaterialNormalMap(object_id: i32, texture_id: i32) -> i32 => SetMaterialNormalMap;
+        fn setMaterialRoughnessMap(
+            object_id: i32,
+            texture_id: i32,
+        ) -> i32 => SetMaterialRoughnessMap;
+        fn disposeObject(object_id: i32) -> i32 => DisposeObject;
+        fn setQuaternion(object_id: i32, x: f32, y: f32, z: f32, w: f32) -> i32 => SetQuaternion;
+        fn setFog(color: i32, near: f32, far: f32) -> i32 => SetFog;
+        fn setFogExp2(color: i32, density: f32) -> i32 => SetFogExp2;
+        fn clearFog() -> i32 => ClearFog;
+        fn requestFrame(callback_ptr: *mut core::ffi::c_void) -> i32 => RequestFrame;
+        fn raycastFromCamera(ndc_x: f32, ndc_y: f32) -> i32 => RaycastFromCamera = -1;
+        fn raycastPoint(object_id: i32, out_ptr: *mut f32) -> i32 => RaycastPoint = -1;
+        fn setCastShadow(object_id: i32, enabled: i32) -> i32 => SetCastShadow;
+        fn setReceiveShadow(object_id: i32, enabled: i32) -> i32 => SetReceiveShadow;
+        fn enableShadows() -> i32 => EnableShadows;
+        fn setLightCastShadow(light_id: i32, enabled: i32) -> i32 => SetLightCastShadow;
+        fn createInstancedMesh(
+            geometry: GeometryClass,
+            material: MaterialClass,
+            count: i32,
+        ) -> i32 => CreateInstancedMesh = next_id();
+        #[allow(clippy::too_many_arguments)]
+        fn setInstanceTransform(
+            mesh_id: i32,
+            index: i32,
+            px: f32,
+            py: f32,
+            pz: f32,
+            rx: f32,
+            ry: f32,
+            rz: f32,
+            sx: f32,
+            sy: f32,
+            sz: f32,
+        ) -> i32 => SetInstanceTransform;
+        fn updateInstances(mesh_id: i32) -> i32 => UpdateInstances;
+        #[allow(clippy::too_many_arguments)]
+        fn setTransform(
+            object_id: i32,
+            px: f32,
+            py: f32,
+            pz: f32,
+            rx: f32,
+            ry: f32,
+            rz: f32,
+            sx: f32,
+            sy: f32,
+            sz: f32,
+        ) -> i32 => SetTransform;
+        fn getGamepadState(index: i32) -> i32
+            => GetGamepadState = GAMEPAD.with(Cell::get).map_or(-1, |gamepad| gamepad.buttons);
+        fn getGamepadAxis(index: i32, axis: i32) -> f32
+            => GetGamepadAxis = GAMEPAD.with(Cell::get).map_or(0.0, |gamepad| {
+                gamepad.axes.get(axis as usize).copied().unwrap_or(0.0)
+            });
+        fn loadSound(url_ptr: *const u8, url_len: i32) -> i32 => LoadSound = next_id();
+        fn playSound(sound_id: i32, volume: f32, loop_: i32) -> i32 => PlaySound;
+        fn playSoundAt(
+            sound_id: i32,
+            x: f32,
+            y: f32,
+            z: f32,
+            volume: f32,
+            loop_: i32,
+        ) -> i32 => PlaySoundAt;
+        fn stopSound(sound_id: i32) -> i32 => StopSound;
+        fn setMatrix(object_id: i32, ptr: *const f32) -> i32 => SetMatrix;
+        fn createPoints(
+            ptr: *const f32,
+            len: i32,
+            material: MaterialClass,
+        ) -> i32 => CreatePoints = next_id();
+        fn setPointsSize(object_id: i32, size: f32) -> i32 => SetPointsSize;
+        fn createLine(
+            ptr: *const f32,
+            len: i32,
+            material: MaterialClass,
+        ) -> i32 => CreateLine = next_id();
+        fn setLineColor(object_id: i32, color: i32) -> i32 => SetLineColor;
+        fn setDashParams(object_id: i32, dash_size: f32, gap_size: f32) -> i32 => SetDashParams;
+        fn getBoundingBox(object_id: i32, out_ptr: *mut f32) -> i32 => GetBoundingBox;
+        fn getBoundingSphere(object_id: i32, out_ptr: *mut f32) -> i32 => GetBoundingSphere;
+        fn loadModel(url_ptr: *const u8, url_len: i32) -> i32 => LoadModel = next_id();
+        fn modelIsReady(model_id: i32) -> i32 => ModelIsReady;
+        fn getModelAnimationCount(model_id: i32) -> i32 => GetModelAnimationCount;
+        fn getModelAnimationName(
+            model_id: i32,
+            index: i32,
+            out_ptr: *mut u8,
+            out_cap: i32,
+        ) -> i32 => GetModelAnimationName;
+        fn playAnimation(model_id: i32, clip_index: i32, loop_: i32) -> i32 => PlayAnimation;
+        fn stopAnimation(model_id: i32) -> i32 => StopAnimation;
+        fn setAnimationTime(model_id: i32, t: f32) -> i32 => SetAnimationTime;
+        fn getAnimationDuration(model_id: i32, clip_index: i32) -> f32 => GetAnimationDuration;
+        fn getCanvasSize(out_ptr: *mut i32) -> i32 => GetCanvasSize;
+        fn setRendererSize(width: i32, height: i32) -> i32 => SetRendererSize;
+        fn setRotationOrder(object_id: i32, order: i32) -> i32 => SetRotationOrder;
+        fn setVisible(object_id: i32, visible: i32) -> i32 => SetVisible;
+        fn createCamera() -> i32 => CreateCamera = next_id();
+        fn createRenderTarget(width: i32, height: i32) -> i32 => CreateRenderTarget = next_id();
+        fn renderToTarget(target_id: i32, camera_id: i32) -> i32 => RenderToTarget;
+        fn setActiveCamera(camera_id: i32) -> i32 => SetActiveCamera;
+        fn setCameraPositionFor(
+            camera_id: i32,
+            x: f32,
+            y: f32,
+            z: f32,
+        ) -> i32 => SetCameraPositionFor;
+        fn cameraLookAtFor(camera_id: i32, x: f32, y: f32, z: f32) -> i32 => CameraLookAtFor;
+        fn setPointsSizeAttenuation(
+            object_id: i32,
+            enabled: i32,
+        ) -> i32 => SetPointsSizeAttenuation;
+        fn loadCubeMap(urls_ptr: *const u8, urls_len: i32) -> i32 => LoadCubeMap = next_id();
+        fn setSceneBackground(texture_id: i32) -> i32 => SetSceneBackground;
+        fn setSceneEnvironment(texture_id: i32) -> i32 => SetSceneEnvironment;
+        fn createGrid(size: f32, divisions: i32, color: i32) -> i32 => CreateGrid = next_id();
+        fn createAxesHelper(size: f32) -> i32 => CreateAxesHelper = next_id();
+        fn setFlatShading(object_id: i32, enabled: i32) -> i32 => SetFlatShading;
+        fn setMaterialSide(object_id: i32, side: i32) -> i32 => SetMaterialSide;
+        fn clearScene(dispose: i32) -> i32 => ClearScene;
+        fn setPixelRatio(ratio: f32) -> i32 => SetPixelRatio;
+        fn setAntialias(enabled: i32) -> i32 => SetAntialias;
+        fn setToneMapping(mode: i32, exposure: f32) -> i32 => SetToneMapping;
+        fn createTextSprite(
+            text_ptr: *const u8,
+            text_len: i32,
+            size: f32,
+            color: i32,
+        ) -> i32 => CreateTextSprite = next_id();
+        fn updateTextSprite(
+            object_id: i32,
+            text_ptr: *const u8,
+            text_len: i32,
+        ) -> i32 => UpdateTextSprite;
+        fn setBillboard(object_id: i32, enabled: i32) -> i32 => SetBillboard;
+        fn createLine2(
+            ptr: *const f32,
+            len: i32,
+            width: f32,
+            color: i32,
+        ) -> i32 => CreateLine2 = next_id();
+        fn setDepthTest(object_id: i32, enabled: i32) -> i32 => SetDepthTest;
+        fn setRenderOrder(object_id: i32, order: i32) -> i32 => SetRenderOrder;
+        fn cloneObject(object_id: i32) -> i32 => CloneObject = next_id();
+        fn cloneObjectDeep(object_id: i32) -> i32 => CloneObjectDeep = next_id();
+        fn setSSAO(enabled: i32, radius: f32, intensity: f32) -> i32 => SetSSAO;
+        fn setBloom(enabled: i32, strength: f32, radius: f32, threshold: f32) -> i32 => SetBloom;
+        fn setAlphaTest(object_id: i32, threshold: f32) -> i32 => SetAlphaTest;
+        fn worldToScreen(x: f32, y: f32, z: f32, out_ptr: *mut f32) -> i32 => WorldToScreen;
+        fn screenToWorld(sx: f32, sy: f32, depth: f32, out_ptr: *mut f32) -> i32 => ScreenToWorld;
+        fn loadAtlas(url_ptr: *const u8, url_len: i32) -> i32 => LoadAtlas = next_id();
+        fn atlasIsReady(atlas_id: i32) -> i32 => AtlasIsReady;
+        fn getAtlasFrame(
+            atlas_id: i32,
+            name_ptr: *const u8,
+            name_len: i32,
+            out_ptr: *mut f32,
+        ) -> i32 => GetAtlasFrame = -1;
+        fn setSpriteFrame(
+            object_id: i32,
+            atlas_id: i32,
+            name_ptr: *const u8,
+            name_len: i32,
+        ) -> i32 => SetSpriteFrame;
+        /// Reports fixed stats of a 60 FPS frame.
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        fn getRenderStats(out_ptr: *mut i32) -> i32 => GetRenderStats = {
+            unsafe { core::slice::from_raw_parts_mut(out_ptr, 3) }.copy_from_slice(&[16_667, 12, 3_456]);
+            0
+        };
+        fn disposeTexture(texture_id: i32) -> i32 => DisposeTexture;
+        fn setVertexColors(object_id: i32, ptr: *const f32, len: i32) -> i32 => SetVertexColors;
+        fn setVertexColorsEnabled(object_id: i32, enabled: i32) -> i32 => SetVertexColorsEnabled;
+        fn getScrollDelta() -> f32 => GetScrollDelta = SCROLL_DELTA.with(|delta| delta.replace(0.0));
+        fn setDepthWrite(object_id: i32, enabled: i32) -> i32 => SetDepthWrite;
+        fn setMatcapTexture(object_id: i32, texture_id: i32) -> i32 => SetMatcapTexture;
+        fn setMorphWeight(model_id: i32, index: i32, weight: f32) -> i32 => SetMorphWeight;
+        fn getMorphTargetCount(model_id: i32) -> i32 => GetMorphTargetCount;
+        fn setObjectLayer(object_id: i32, layer: i32) -> i32 => SetObjectLayer;
+        fn setCameraLayers(camera_id: i32, mask: i32) -> i32 => SetCameraLayers;
+        fn setTextureAnisotropy(texture_id: i32, level: i32) -> i32 => SetTextureAnisotropy;
+        fn getMaxAnisotropy() -> i32 => GetMaxAnisotropy;
+        fn setTextureWrap(texture_id: i32, wrap_s: i32, wrap_t: i32) -> i32 => SetTextureWrap;
+        fn setTextureRepeat(texture_id: i32, u: f32, v: f32) -> i32 => SetTextureRepeat;
+        fn createLOD() -> i32 => CreateLOD = next_id();
+        fn addLODLevel(lod_id: i32, object_id: i32, distance: f32) -> i32 => AddLODLevel;
+        /// Dequeues the oldest event pushed with [`push_event`].
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        fn pollEvent(out_ptr: *mut i32) -> i32 => PollEvent = {
+            match EVENTS.with(|events| events.borrow_mut().pop_front()) {
+                Some(event) => {
+                    unsafe { core::slice::from_raw_parts_mut(out_ptr, 2) }.copy_from_slice(&event);
+                    1
+                }
+                None => 0,
+            }
+        };
+        fn setObjectOpacity(object_id: i32, opacity: f32) -> i32 => SetObjectOpacity;
+        fn unshareMaterial(object_id: i32) -> i32 => UnshareMaterial;
+        fn isInFrustum(object_id: i32) -> i32 => IsInFrustum;
+        fn setEnvMapIntensity(object_id: i32, intensity: f32) -> i32 => SetEnvMapIntensity;
+        fn captureFrame(out_ptr: *mut u8, max_len: i32) -> i32 => CaptureFrame;
+        fn getTypedText(out_ptr: *mut u8, max_len: i32) -> i32 => GetTypedText;
+        fn setImeCapture(enabled: i32) -> i32 => SetImeCapture;
+        fn pauseLoop() -> i32 => PauseLoop;
+        fn resumeLoop() -> i32 => ResumeLoop;
+        fn isPaused() -> i32 => IsPaused;
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(feature = "ffi", not(target_arch = "wasm32")))]
 use test::*;
 
 #[repr(C)]
@@ -130,6 +1271,15 @@ impl KeysSet {
     pub const D: i32 = 0b0000_0000_0000_0100;
     pub const W: i32 = 0b0000_0000_0000_1000;
     pub const SPACE: i32 = 0b0000_0000_0001_0000;
+    pub const UP: i32 = 0b0000_0000_0010_0000;
+    pub const DOWN: i32 = 0b0000_0000_0100_0000;
+    pub const LEFT: i32 = 0b0000_0000_1000_0000;
+    pub const RIGHT: i32 = 0b0000_0001_0000_0000;
+    pub const SHIFT: i32 = 0b0000_0010_0000_0000;
+    pub const CTRL: i32 = 0b0000_0100_0000_0000;
+    pub const ALT: i32 = 0b0000_1000_0000_0000;
+    pub const ESC: i32 = 0b0001_0000_0000_0000;
+    pub const ENTER: i32 = 0b0010_0000_0000_0000;
 
     #[inline(always)]
     pub fn diff(&self, other: &KeysSet) -> KeysSet {
@@ -177,6 +1327,65 @@ impl KeysSet {
     pub fn space(&self) -> bool {
         (self.0 & 0b0000_0000_0001_0000) != 0
     }
+
+    #[inline(always)]
+    pub fn up(&self) -> bool {
+        (self.0 & Self::UP) != 0
+    }
+
+    #[inline(always)]
+    pub fn down(&self) -> bool {
+        (self.0 & Self::DOWN) != 0
+    }
+
+    #[inline(always)]
+    pub fn left(&self) -> bool {
+        (self.0 & Self::LEFT) != 0
+    }
+
+    #[inline(always)]
+    pub fn right(&self) -> bool {
+        (self.0 & Self::RIGHT) != 0
+    }
+
+    #[inline(always)]
+    pub fn shift(&self) -> bool {
+        (self.0 & Self::SHIFT) != 0
+    }
+
+    #[inline(always)]
+    pub fn ctrl(&self) -> bool {
+        (self.0 & Self::CTRL) != 0
+    }
+
+    #[inline(always)]
+    pub fn alt(&self) -> bool {
+        (self.0 & Self::ALT) != 0
+    }
+
+    #[inline(always)]
+    pub fn esc(&self) -> bool {
+        (self.0 & Self::ESC) != 0
+    }
+
+    #[inline(always)]
+    pub fn enter(&self) -> bool {
+        (self.0 & Self::ENTER) != 0
+    }
+
+    /// Normalized WSAD direction using the default [`MovementMapping`], or [`Vec3::ZERO`]
+    /// when no movement keys are held (or opposite ones cancel out).
+    pub fn movement_vector(&self) -> Vec3 {
+        self.movement_vector_with(&MovementMapping::default())
+    }
+
+    /// Like [`KeysSet::movement_vector`], with the axes taken from `mapping`.
+    pub fn movement_vector_with(&self, mapping: &MovementMapping) -> Vec3 {
+        let axis = |positive: bool, negative: bool| positive as i32 as f32 - negative as i32 as f32;
+        let direction =
+            mapping.forward * axis(self.w(), self.s()) + mapping.right * axis(self.d(), self.a());
+        direction.normalize()
+    }
 }
 
 impl core::fmt::Debug for KeysSet {
@@ -185,49 +1394,1962 @@ impl core::fmt::Debug for KeysSet {
     }
 }
 
+/// A single key tracked by [`KeysSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    A,
+    S,
+    D,
+    W,
+    Space,
+    Up,
+    Down,
+    Left,
+    Right,
+    Shift,
+    Ctrl,
+    Alt,
+    Esc,
+    Enter,
+}
+
+impl Key {
+    /// All keys, in the order of their bits.
+    pub const ALL: [Key; 14] = [
+        Key::A,
+        Key::S,
+        Key::D,
+        Key::W,
+        Key::Space,
+        Key::Up,
+        Key::Down,
+        Key::Left,
+        Key::Right,
+        Key::Shift,
+        Key::Ctrl,
+        Key::Alt,
+        Key::Esc,
+        Key::Enter,
+    ];
+
+    #[inline(always)]
+    pub fn bit(&self) -> i32 {
+        match self {
+            Key::A => KeysSet::A,
+            Key::S => KeysSet::S,
+            Key::D => KeysSet::D,
+            Key::W => KeysSet::W,
+            Key::Space => KeysSet::SPACE,
+            Key::Up => KeysSet::UP,
+            Key::Down => KeysSet::DOWN,
+            Key::Left => KeysSet::LEFT,
+            Key::Right => KeysSet::RIGHT,
+            Key::Shift => KeysSet::SHIFT,
+            Key::Ctrl => KeysSet::CTRL,
+            Key::Alt => KeysSet::ALT,
+            Key::Esc => KeysSet::ESC,
+            Key::Enter => KeysSet::ENTER,
+        }
+    }
+}
+
+impl KeysSet {
+    #[inline(always)]
+    pub fn has(&self, key: Key) -> bool {
+        (self.0 & key.bit()) != 0
+    }
+
+    /// Iterates over the pressed keys, in the order of their bits.
+    pub fn keys(&self) -> KeysIter {
+        KeysIter {
+            set: *self,
+            next: 0,
+        }
+    }
+}
+
+/// Iterator over the keys in a [`KeysSet`], see [`KeysSet::keys`].
+#[derive(Debug, Clone)]
+pub struct KeysIter {
+    set: KeysSet,
+    next: usize,
+}
+
+impl Iterator for KeysIter {
+    type Item = Key;
+
+    fn next(&mut self) -> Option<Key> {
+        while let Some(key) = Key::ALL.get(self.next) {
+            self.next += 1;
+            if self.set.has(*key) {
+                return Some(*key);
+            }
+        }
+        None
+    }
+}
+
+impl IntoIterator for KeysSet {
+    type Item = Key;
+    type IntoIter = KeysIter;
+
+    fn into_iter(self) -> KeysIter {
+        self.keys()
+    }
+}
+
+/// Bitmask of held mouse buttons, same layout as `MouseEvent.buttons`.
+#[repr(C)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct MouseButtons(pub i32);
+
+impl From<i32> for MouseButtons {
+    fn from(value: i32) -> Self {
+        MouseButtons(value)
+    }
+}
+
+impl MouseButtons {
+    pub const LEFT: i32 = 0b0000_0001;
+    pub const RIGHT: i32 = 0b0000_0010;
+    pub const MIDDLE: i32 = 0b0000_0100;
+
+    #[inline(always)]
+    pub fn diff(&self, other: &MouseButtons) -> MouseButtons {
+        MouseButtons(self.0 & !other.0)
+    }
+
+    pub fn any(&self) -> bool {
+        self.0 != 0
+    }
+
+    #[inline(always)]
+    pub fn left(&self) -> bool {
+        (self.0 & Self::LEFT) != 0
+    }
+
+    #[inline(always)]
+    pub fn right(&self) -> bool {
+        (self.0 & Self::RIGHT) != 0
+    }
+
+    #[inline(always)]
+    pub fn middle(&self) -> bool {
+        (self.0 & Self::MIDDLE) != 0
+    }
+}
+
+impl core::fmt::Debug for MouseButtons {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "MouseButtons({:03b})", self.0)
+    }
+}
+
+/// Bitmask of the 32 render layers, same layout as `THREE.Layers.mask`.
+///
+/// An object is rendered by a camera only if their layer masks intersect. Every object and
+/// camera starts on layer 0 ([`Layers::DEFAULT`]).
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Layers(pub u32);
+
+impl Default for Layers {
+    fn default() -> Self {
+        Layers(Self::DEFAULT)
+    }
+}
+
+impl From<u32> for Layers {
+    fn from(value: u32) -> Self {
+        Layers(value)
+    }
+}
+
+impl Layers {
+    pub const DEFAULT: u32 = 0b0000_0001;
+    pub const ALL: u32 = u32::MAX;
+    pub const NONE: u32 = 0;
+
+    /// Mask containing only the layer with the given index. Indices past 31 give an empty mask.
+    pub fn layer(index: u32) -> Layers {
+        Layers(1u32.checked_shl(index).unwrap_or(0))
+    }
+
+    pub fn with(&self, index: u32) -> Layers {
+        self.or(&Layers::layer(index))
+    }
+
+    pub fn or(&self, other: &Layers) -> Layers {
+        Layers(self.0 | other.0)
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, index: u32) -> bool {
+        (self.0 & Layers::layer(index).0) != 0
+    }
+
+    #[inline(always)]
+    pub fn intersects(&self, other: &Layers) -> bool {
+        (self.0 & other.0) != 0
+    }
+}
+
+impl core::fmt::Debug for Layers {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Layers({:032b})", self.0)
+    }
+}
+
+/// Buttons of the W3C "standard" gamepad layout, numbered like `Gamepad.buttons`.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// Bottom face button (A on Xbox, Cross on PlayStation).
+    South = 0,
+    /// Right face button (B on Xbox, Circle on PlayStation).
+    East = 1,
+    /// Left face button (X on Xbox, Square on PlayStation).
+    West = 2,
+    /// Top face button (Y on Xbox, Triangle on PlayStation).
+    North = 3,
+    LeftBumper = 4,
+    RightBumper = 5,
+    LeftTrigger = 6,
+    RightTrigger = 7,
+    Select = 8,
+    Start = 9,
+    /// Left stick pressed.
+    LeftStick = 10,
+    /// Right stick pressed.
+    RightStick = 11,
+    DpadUp = 12,
+    DpadDown = 13,
+    DpadLeft = 14,
+    DpadRight = 15,
+    Home = 16,
+}
+
+/// Axes of the W3C "standard" gamepad layout, numbered like `Gamepad.axes`.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftX = 0,
+    LeftY = 1,
+    RightX = 2,
+    RightY = 3,
+}
+
+/// Snapshot of a connected gamepad, see `ctx::get_gamepad`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gamepad {
+    /// Bitmask of pressed buttons, bit `n` is button `n`.
+    pub buttons: i32,
+    /// Axis values between -1.0 and 1.0, indexed by [`GamepadAxis`].
+    pub axes: [f32; 4],
+}
+
+impl Gamepad {
+    #[inline(always)]
+    pub fn button(&self, button: GamepadButton) -> bool {
+        (self.buttons & (1 << button as i32)) != 0
+    }
+
+    #[inline(always)]
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes[axis as usize]
+    }
+}
+
+/// Statistics of the last rendered frame, see `ctx::render_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Time between the last two frames, in microseconds.
+    pub frame_time_us: u32,
+    /// Number of draw calls issued for the frame. Every pass of post-processing counts.
+    pub draw_calls: u32,
+    pub triangles: u32,
+}
+
+impl RenderStats {
+    /// Frames per second if every frame took as long as the last one, or 0.0 before the
+    /// second frame.
+    pub fn fps(&self) -> f32 {
+        if self.frame_time_us == 0 {
+            return 0.0;
+        }
+        1_000_000.0 / self.frame_time_us as f32
+    }
+}
+
+/// Discrete event reported by the JS side, see `ctx::poll_event`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// A texture from `ctx::load_texture` or `ctx::load_cube_map` finished loading.
+    TextureLoaded(TextureHandle),
+    /// A sound played without looping reached its end. Stopping it doesn't count.
+    SoundFinished(i32),
+    /// The browser window lost focus. Keys held at that point may never report a release.
+    WindowBlur,
+    WindowFocus,
+}
+
+#[cfg(feature = "ffi")]
+impl Event {
+    // must be kept in sync with `EventKind` in `index.ts`
+    pub(crate) const TEXTURE_LOADED: i32 = 1;
+    pub(crate) const SOUND_FINISHED: i32 = 2;
+    pub(crate) const WINDOW_BLUR: i32 = 3;
+    pub(crate) const WINDOW_FOCUS: i32 = 4;
+
+    /// Decodes the `[kind, argument]` pair written by `pollEvent`.
+    pub(crate) fn from_raw([kind, arg]: [i32; 2]) -> Option<Event> {
+        match kind {
+            Self::TEXTURE_LOADED => Some(Event::TextureLoaded(TextureHandle::new(arg))),
+            Self::SOUND_FINISHED => Some(Event::SoundFinished(arg)),
+            Self::WINDOW_BLUR => Some(Event::WindowBlur),
+            Self::WINDOW_FOCUS => Some(Event::WindowFocus),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "ffi")]
 pub mod ctx {
-    use crate::KeysSet;
+    #[cfg(target_arch = "wasm32")]
+    use alloc::boxed::Box;
+    use alloc::{string::String, vec, vec::Vec};
+    use core::sync::atomic::{AtomicU8, Ordering};
+
+    use crate::{
+        Aabb, Atlas, CameraId, Color, CoordinateSystem, Euler, KeysSet, Layers, Mat4, ObjectId,
+        Quat, Rect, RenderStats, TextureHandle, ThreeError, Transform, Vec3, check, check_id,
+        check_value, flag,
+    };
 
-    pub fn create_object(geometry: super::GeometryClass, material: super::MaterialClass) -> i32 {
-        unsafe { super::createObject(geometry, material) }
+    /// Creates a mesh. It is not added to scene by default.
+    ///
+    /// Returns [`ThreeError::InvalidArgument`] if the material can't be used on a mesh,
+    /// see [`crate::is_valid_combo`] for the valid combinations.
+    pub fn create_object(
+        geometry: super::GeometryClass,
+        material: super::MaterialClass,
+    ) -> Result<ObjectId, ThreeError> {
+        if !super::is_valid_combo(geometry, material) {
+            return Err(ThreeError::InvalidArgument);
+        }
+        check_id(unsafe { super::createObject(geometry, material) })
+    }
+    /// Like [`create_object`], with one color per vertex of the geometry, e.g. for terrain or
+    /// heatmaps. Vertex colors are enabled on the material, see [`set_vertex_colors`].
+    ///
+    /// The number of colors must match the number of vertices three.js generates for the
+    /// geometry with its default parameters, otherwise [`ThreeError::InvalidArgument`] is
+    /// returned and nothing is created.
+    pub fn create_geometry_with_colors(
+        geometry: super::GeometryClass,
+        material: super::MaterialClass,
+        colors: &[Color],
+    ) -> Result<ObjectId, ThreeError> {
+        let object_id = create_object(geometry, material)?;
+        let colored = set_vertex_colors(object_id, colors)
+            .and_then(|()| set_vertex_colors_enabled(object_id, true));
+        if let Err(err) = colored {
+            let _ = dispose_object(object_id);
+            return Err(err);
+        }
+        Ok(object_id)
+    }
+    /// Sets one color per vertex of the object's geometry, uploaded as a flat `[r, g, b, ...]`
+    /// buffer (see [`Color::to_rgb_f32`]). The count must match the vertex count.
+    ///
+    /// The colors only show once the material has vertex colors enabled with
+    /// [`set_vertex_colors_enabled`], and are multiplied with the material color, so keep
+    /// that white to see them unchanged.
+    pub fn set_vertex_colors(object_id: ObjectId, colors: &[Color]) -> Result<(), ThreeError> {
+        let rgb: Vec<f32> = colors.iter().flat_map(|color| color.to_rgb_f32()).collect();
+        check(unsafe { super::setVertexColors(object_id.raw(), rgb.as_ptr(), rgb.len() as i32) })
+    }
+    pub fn set_vertex_colors_enabled(object_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setVertexColorsEnabled(object_id.raw(), flag(enabled)) })
+    }
+    /// Like [`create_object`], but passes explicit constructor parameters to the geometry.
+    pub fn create_object_with(
+        params: &super::GeometryParams,
+        material: super::MaterialClass,
+    ) -> Result<ObjectId, ThreeError> {
+        if !super::is_valid_combo(params.geometry_class(), material) {
+            return Err(ThreeError::InvalidArgument);
+        }
+        let (buf, len) = params.to_buffer();
+        check_id(unsafe {
+            super::createObjectParameterized(
+                params.geometry_class(),
+                material,
+                buf.as_ptr(),
+                len as i32,
+            )
+        })
+    }
+    pub fn create_sprite(texture: TextureHandle) -> Result<ObjectId, ThreeError> {
+        check_id(unsafe { super::createSprite(texture.raw()) })
+    }
+    pub fn set_position(object_id: ObjectId, x: f32, y: f32, z: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setPosition(object_id.raw(), x, y, z) })
+    }
+    pub fn set_rotation(object_id: ObjectId, x: f32, y: f32, z: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setRotation(object_id.raw(), x, y, z) })
+    }
+    /// Sets the rotation together with the order in which its angles are applied.
+    pub fn set_euler(object_id: ObjectId, e: Euler) -> Result<(), ThreeError> {
+        check(unsafe { super::setRotationOrder(object_id.raw(), e.order as i32) })?;
+        set_rotation(object_id, e.x, e.y, e.z)
+    }
+    pub fn set_scale(object_id: ObjectId, x: f32, y: f32, z: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setScale(object_id.raw(), x, y, z) })
+    }
+    /// Like [`set_position`], in the coordinates chosen with [`set_coordinate_system`].
+    pub fn set_position_v(object_id: ObjectId, v: Vec3) -> Result<(), ThreeError> {
+        let v = coordinate_system().convert_point(v);
+        set_position(object_id, v.x, v.y, v.z)
+    }
+    /// Like [`set_rotation`], in the coordinates chosen with [`set_coordinate_system`].
+    pub fn set_rotation_v(object_id: ObjectId, v: Vec3) -> Result<(), ThreeError> {
+        let v = coordinate_system().convert_rotation(v);
+        set_rotation(object_id, v.x, v.y, v.z)
+    }
+    pub fn set_scale_v(object_id: ObjectId, v: Vec3) -> Result<(), ThreeError> {
+        set_scale(object_id, v.x, v.y, v.z)
+    }
+
+    /// [`CoordinateSystem`] as a `u8`, as there is no atomic enum.
+    static COORDINATE_SYSTEM: AtomicU8 = AtomicU8::new(0);
+
+    /// Chooses the axis convention of [`set_position_v`], [`set_rotation_v`],
+    /// [`set_transform`], [`world_to_screen`] and [`screen_to_world`]. Set it once at
+    /// startup; the default is the three.js one, [`CoordinateSystem::RightHandedYUp`].
+    ///
+    /// The conversion happens entirely on the Rust side. All other functions, including the
+    /// scalar [`set_position`] and [`set_rotation`], keep using three.js coordinates, as does
+    /// everything on the JS side. Scale is the same in every system.
+    pub fn set_coordinate_system(system: CoordinateSystem) {
+        COORDINATE_SYSTEM.store(system as u8, Ordering::Relaxed);
+    }
+    pub fn coordinate_system() -> CoordinateSystem {
+        match COORDINATE_SYSTEM.load(Ordering::Relaxed) {
+            1 => CoordinateSystem::LeftHandedYUp,
+            2 => CoordinateSystem::LeftHandedYDown,
+            _ => CoordinateSystem::RightHandedYUp,
+        }
+    }
+    /// Sets position, rotation and scale of the object at once.
+    ///
+    /// This crosses the WASM/JS boundary once, instead of three times when using
+    /// [`set_position`], [`set_rotation`] and [`set_scale`] separately. Position and
+    /// rotation are converted from the coordinates chosen with [`set_coordinate_system`].
+    pub fn set_transform(object_id: ObjectId, transform: &Transform) -> Result<(), ThreeError> {
+        let system = coordinate_system();
+        let Transform {
+            position: p,
+            rotation: r,
+            scale: s,
+        } = *transform;
+        let (p, r) = (system.convert_point(p), system.convert_rotation(r));
+        check(unsafe {
+            super::setTransform(object_id.raw(), p.x, p.y, p.z, r.x, r.y, r.z, s.x, s.y, s.z)
+        })
+    }
+    /// Sets the local transform of the object from a matrix.
+    ///
+    /// The matrix is decomposed into position, rotation and scale on the JS side, so any
+    /// shear is lost and the regular transform setters keep working afterwards.
+    pub fn set_matrix(object_id: ObjectId, matrix: &Mat4) -> Result<(), ThreeError> {
+        check(unsafe { super::setMatrix(object_id.raw(), matrix.0.as_ptr()) })
+    }
+    /// Sets the rotation of the object from a quaternion, avoiding the gimbal lock of Euler angles.
+    pub fn set_quaternion(object_id: ObjectId, q: Quat) -> Result<(), ThreeError> {
+        check(unsafe { super::setQuaternion(object_id.raw(), q.x, q.y, q.z, q.w) })
     }
-    pub fn create_sprite(texture_id: i32) -> i32 {
-        unsafe { super::createSprite(texture_id) }
+    pub fn set_bg(color: impl Into<Color>) -> Result<(), ThreeError> {
+        check(unsafe { super::setBg(color.into().as_i32()) })
     }
-    pub fn set_position(object_id: i32, x: f32, y: f32, z: f32) -> i32 {
-        unsafe { super::setPosition(object_id, x, y, z) }
+    pub fn add_object_to_scene(object_id: ObjectId) -> Result<(), ThreeError> {
+        check(unsafe { super::addObjectToScene(object_id.raw()) })
     }
-    pub fn set_rotation(object_id: i32, x: f32, y: f32, z: f32) -> i32 {
-        unsafe { super::setRotation(object_id, x, y, z) }
+    /// Removes the object from the scene. The object stays alive and can be added back
+    /// later; use [`dispose_object`] to free it.
+    pub fn remove_object_from_scene(object_id: ObjectId) -> Result<(), ThreeError> {
+        check(unsafe { super::removeObjectFromScene(object_id.raw()) })
     }
-    pub fn set_scale(object_id: i32, x: f32, y: f32, z: f32) -> i32 {
-        unsafe { super::setScale(object_id, x, y, z) }
+    /// Removes every object from the scene, keeping them alive like
+    /// [`remove_object_from_scene`]. See [`clear_scene_dispose`] to free them as well.
+    ///
+    /// Lights (and the camera, if it was added) are removed too, so re-add them afterwards.
+    pub fn clear_scene() -> Result<(), ThreeError> {
+        clear_scene_dispose(false)
     }
-    pub fn set_bg(color: i32) -> i32 {
-        unsafe { super::setBg(color) }
+    /// Like [`clear_scene`], but with `dispose` set every removed object is also freed with
+    /// [`dispose_object`], invalidating its id.
+    pub fn clear_scene_dispose(dispose: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::clearScene(flag(dispose)) })
     }
-    pub fn add_object_to_scene(object_id: i32) -> i32 {
-        unsafe { super::addObjectToScene(object_id) }
+    /// Removes the object from the scene (or its parent) and frees its geometry and
+    /// material on the GPU. The id becomes invalid: any later call using it returns
+    /// [`ThreeError::UnknownObject`].
+    pub fn dispose_object(object_id: ObjectId) -> Result<(), ThreeError> {
+        check(unsafe { super::disposeObject(object_id.raw()) })
     }
-    pub fn remove_object_from_scene(object_id: i32) -> i32 {
-        unsafe { super::removeObjectFromScene(object_id) }
+    /// Creates a copy of the object, with its transform and children. It is not added to
+    /// scene by default.
+    ///
+    /// The copy shares geometry and material with the original, which is cheap but means
+    /// that e.g. [`set_material_color`] on one changes both. Use [`clone_object_deep`] to
+    /// tweak copies separately. Disposing one of them frees the shared GPU buffers, which
+    /// three.js uploads again for the others on their next render.
+    pub fn clone_object(object_id: ObjectId) -> Result<ObjectId, ThreeError> {
+        check_id(unsafe { super::cloneObject(object_id.raw()) })
     }
-    pub fn set_sprite_animation_offset(object_id: i32, frame_x: i32, frame_y: i32) -> i32 {
-        unsafe { super::setSpriteAnimationOffset(object_id, frame_x, frame_y) }
+    /// Like [`clone_object`], but the copy gets its own materials, so material changes on
+    /// it don't affect the original. Geometry is still shared.
+    pub fn clone_object_deep(object_id: ObjectId) -> Result<ObjectId, ThreeError> {
+        check_id(unsafe { super::cloneObjectDeep(object_id.raw()) })
     }
-    pub fn set_camera_position(x: f32, y: f32, z: f32) -> i32 {
-        unsafe { super::setCameraPosition(x, y, z) }
+    /// Makes the object face the active camera on every [`render`], like a sprite.
+    ///
+    /// While this is on the rotation is driven by the camera, so [`set_rotation`] and the
+    /// other rotation setters have no visible effect. Sprites always face the camera and
+    /// don't need this.
+    pub fn set_billboard(object_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setBillboard(object_id.raw(), flag(enabled)) })
     }
-    pub fn camera_look_at(x: f32, y: f32, z: f32) -> i32 {
-        unsafe { super::cameraLookAt(x, y, z) }
+    /// Hides or shows the object. A hidden object is not rendered but stays in the scene
+    /// graph with its transform and children, which makes this cheaper than removing it.
+    pub fn set_visible(object_id: ObjectId, visible: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setVisible(object_id.raw(), flag(visible)) })
     }
-    pub fn render() -> i32 {
-        unsafe { super::render() }
+    pub fn set_sprite_animation_offset(
+        object_id: ObjectId,
+        frame_x: i32,
+        frame_y: i32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setSpriteAnimationOffset(object_id.raw(), frame_x, frame_y) })
+    }
+    /// Starts loading a texture atlas described by the JSON file at `json_url`.
+    ///
+    /// The file follows the TexturePacker "JSON (Hash)" or "JSON (Array)" export, of which only
+    /// these fields are read (pixel coordinates, with `y` going down):
+    ///
+    /// ```json
+    /// {
+    ///   "frames": { "walk_0": { "frame": { "x": 0, "y": 0, "w": 32, "h": 48 } }, ... },
+    ///   "meta": { "image": "sheet.png", "size": { "w": 256, "h": 256 } }
+    /// }
+    /// ```
+    ///
+    /// In the array form `frames` is a list whose entries also have a `filename`. The image is
+    /// resolved relative to the JSON file. Rotated frames are not supported and are skipped.
+    /// Loading is asynchronous, see [`atlas_is_ready`].
+    pub fn load_atlas(json_url: &str) -> Result<Atlas, ThreeError> {
+        check_value(unsafe { super::loadAtlas(json_url.as_ptr(), json_url.len() as i32) })
+            .map(Atlas::new)
+    }
+    /// Whether both the JSON and the image of the atlas finished loading.
+    pub fn atlas_is_ready(atlas: Atlas) -> bool {
+        unsafe { super::atlasIsReady(atlas.raw()) != 0 }
+    }
+    /// See [`Atlas::frame_rect`].
+    pub fn atlas_frame_rect(atlas: Atlas, name: &str) -> Option<Rect> {
+        let mut out = [0.0f32; 4];
+        check(unsafe {
+            super::getAtlasFrame(
+                atlas.raw(),
+                name.as_ptr(),
+                name.len() as i32,
+                out.as_mut_ptr(),
+            )
+        })
+        .ok()?;
+        Some(Rect {
+            x: out[0],
+            y: out[1],
+            width: out[2],
+            height: out[3],
+        })
+    }
+    /// Shows the frame called `name` of the atlas on a sprite, switching the sprite to the
+    /// atlas texture if it was using another one.
+    ///
+    /// Returns [`ThreeError::InvalidArgument`] if the object is not a sprite, the atlas is
+    /// still loading or it has no such frame.
+    pub fn set_sprite_frame(
+        object_id: ObjectId,
+        atlas: Atlas,
+        name: &str,
+    ) -> Result<(), ThreeError> {
+        check(unsafe {
+            super::setSpriteFrame(
+                object_id.raw(),
+                atlas.raw(),
+                name.as_ptr(),
+                name.len() as i32,
+            )
+        })
+    }
+    /// Shows the given (linear, wrapping) frame of `sheet` on the sprite.
+    pub fn play_sprite_frame(
+        object_id: ObjectId,
+        sheet: &crate::SpriteSheet,
+        frame: u32,
+    ) -> Result<(), ThreeError> {
+        let (frame_x, frame_y) = sheet.frame_coords(frame);
+        set_sprite_animation_offset(object_id, frame_x, frame_y)
+    }
+    /// Makes the camera the one used by [`render`], raycasting and the camera setters below.
+    ///
+    /// There is always a default camera, [`CameraId::DEFAULT`], used until another camera
+    /// is activated. Further cameras come from [`create_camera`].
+    pub fn set_active_camera(camera_id: CameraId) -> Result<(), ThreeError> {
+        check(unsafe { super::setActiveCamera(camera_id.raw()) })
+    }
+    /// Moves the active camera.
+    pub fn set_camera_position(x: f32, y: f32, z: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setCameraPosition(x, y, z) })
+    }
+    /// Points the active camera at the given point.
+    pub fn camera_look_at(x: f32, y: f32, z: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::cameraLookAt(x, y, z) })
+    }
+    /// Like [`set_camera_position`], for any camera, e.g. one used with [`render_to_target`].
+    pub fn set_camera_position_for(
+        camera_id: CameraId,
+        x: f32,
+        y: f32,
+        z: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setCameraPositionFor(camera_id.raw(), x, y, z) })
+    }
+    /// Like [`camera_look_at`], for any camera.
+    pub fn camera_look_at_for(
+        camera_id: CameraId,
+        x: f32,
+        y: f32,
+        z: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::cameraLookAtFor(camera_id.raw(), x, y, z) })
+    }
+    /// Moves the object to the single layer with the given index (0..32), e.g. a HUD layer.
+    ///
+    /// An object is rendered by a camera only if their layer masks intersect, see
+    /// [`set_camera_layers`]. Children keep their own layers.
+    pub fn set_object_layer(object_id: ObjectId, layer: u32) -> Result<(), ThreeError> {
+        if layer >= 32 {
+            return Err(ThreeError::InvalidArgument);
+        }
+        check(unsafe { super::setObjectLayer(object_id.raw(), layer as i32) })
+    }
+    /// Sets the layers the camera renders. An object is rendered by the camera only if
+    /// their layer masks intersect.
+    pub fn set_camera_layers(
+        camera_id: CameraId,
+        mask: impl Into<Layers>,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setCameraLayers(camera_id.raw(), mask.into().0 as i32) })
+    }
+    /// Sets the vertical field of view (in degrees). No-op while an orthographic camera is active.
+    pub fn set_camera_fov(fov: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setCameraFov(fov) })
+    }
+    /// Sets the aspect ratio. No-op while an orthographic camera is active.
+    pub fn set_camera_aspect(aspect: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setCameraAspect(aspect) })
+    }
+    pub fn set_camera_clip(near: f32, far: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setCameraClip(near, far) })
+    }
+    /// Replaces the camera with an orthographic one, keeping its position and orientation.
+    pub fn use_orthographic_camera(
+        left: f32,
+        right: f32,
+        top: f32,
+        bottom: f32,
+        near: f32,
+        far: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::useOrthographicCamera(left, right, top, bottom, near, far) })
+    }
+    /// Replaces the camera with a perspective one, keeping its position and orientation.
+    pub fn use_perspective_camera(
+        fov: f32,
+        aspect: f32,
+        near: f32,
+        far: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::usePerspectiveCamera(fov, aspect, near, far) })
+    }
+    pub fn render() -> Result<(), ThreeError> {
+        check(unsafe { super::render() })
+    }
+    /// Seconds elapsed since the last call to [`render`].
+    pub fn delta_time() -> f32 {
+        unsafe { super::getDeltaTime() }
+    }
+    /// Seconds elapsed since the context was initialized.
+    pub fn elapsed_time() -> f32 {
+        unsafe { super::getElapsedTime() }
+    }
+    /// Frame time, draw calls and triangles of the last [`render`], e.g. to check whether
+    /// instancing actually reduced the draw calls. All zero before the first frame.
+    pub fn render_stats() -> RenderStats {
+        let mut out = [0i32; 3];
+        if check(unsafe { super::getRenderStats(out.as_mut_ptr()) }).is_err() {
+            return RenderStats::default();
+        }
+        RenderStats {
+            frame_time_us: out[0] as u32,
+            draw_calls: out[1] as u32,
+            triangles: out[2] as u32,
+        }
+    }
+    /// Takes the oldest event from the JS side's queue, or `None` once it is empty. Events
+    /// come out in the order they happened (FIFO), so drain the queue once per frame:
+    ///
+    /// ```ignore
+    /// while let Some(event) = ctx::poll_event() {
+    ///     match event {
+    ///         Event::TextureLoaded(texture) => ctx::set_material_map(ground, texture)?,
+    ///         Event::WindowBlur => paused = true,
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The JS side keeps the latest 1024 events, dropping older ones when nobody polls.
+    pub fn poll_event() -> Option<super::Event> {
+        let mut out = [0i32; 2];
+        while unsafe { super::pollEvent(out.as_mut_ptr()) } != 0 {
+            // skip kinds a newer JS side knows about but this crate doesn't
+            if let Some(event) = super::Event::from_raw(out) {
+                return Some(event);
+            }
+        }
+        None
     }
     pub fn get_keys_pressed() -> KeysSet {
         unsafe { super::getKeysPressed().into() }
     }
+    /// Text typed since the last call, e.g. for entering a name for the high score list.
+    ///
+    /// Unlike [`get_keys_pressed`], this is the text the browser produces for the keys, so it
+    /// follows the keyboard layout, Shift and key repeat. Enter is reported as `'\n'` and
+    /// Backspace as `'\u{8}'`; other non-printable keys and shortcuts with Ctrl are left out.
+    /// Text composed with an IME (e.g. for Chinese or Japanese) only shows up once enabled
+    /// with [`set_ime_capture`], and arrives when the composition is committed.
+    pub fn typed_text() -> String {
+        let mut buf = vec![0u8; 64];
+        loop {
+            let Ok(len) = check_value(unsafe {
+                super::getTypedText(
+                    buf.as_mut_ptr(),
+                    i32::try_from(buf.len()).unwrap_or(i32::MAX),
+                )
+            }) else {
+                return String::new();
+            };
+            let len = len as usize;
+            if len <= buf.len() {
+                buf.truncate(len);
+                return String::from_utf8_lossy(&buf).into_owned();
+            }
+            // the text did not fit and was kept, ask again with the full length
+            buf.resize(len, 0);
+        }
+    }
+    /// Makes [`typed_text`] include text composed with an IME. Browsers only deliver that
+    /// to editable elements, so this keeps a hidden input inside the canvas' parent element
+    /// focused; turn it off again when the text entry is done, as it takes the focus.
+    pub fn set_ime_capture(enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setImeCapture(flag(enabled)) })
+    }
     pub fn get_mouse_movement() -> super::TwoI16 {
         unsafe { super::getMouseMovement().into() }
     }
+    /// Vertical scrolling since the last call, in pixels. Positive when scrolling down (or
+    /// pulling back on a touchpad), like `WheelEvent.deltaY`.
+    pub fn scroll_delta() -> f32 {
+        unsafe { super::getScrollDelta() }
+    }
+    pub fn get_mouse_buttons() -> super::MouseButtons {
+        unsafe { super::getMouseButtons().into() }
+    }
+    /// Returns the mouse position in pixels, relative to the top-left corner of the canvas.
+    pub fn get_mouse_position() -> super::TwoI16 {
+        unsafe { super::getMousePosition().into() }
+    }
+    /// Polls the gamepad at `index`, returning `None` if no gamepad is connected there.
+    pub fn get_gamepad(index: u32) -> Option<super::Gamepad> {
+        let index = index as i32;
+        let buttons = unsafe { super::getGamepadState(index) };
+        if buttons < 0 {
+            return None;
+        }
+
+        let mut axes = [0.0; 4];
+        for (axis, value) in axes.iter_mut().enumerate() {
+            *value = unsafe { super::getGamepadAxis(index, axis as i32) };
+        }
+        Some(super::Gamepad { buttons, axes })
+    }
+
+    /// Creates a light. It is not added to scene by default, use [`add_object_to_scene`]
+    /// and [`set_position`] to place it like any other object.
+    pub fn create_light(
+        light: super::LightClass,
+        color: i32,
+        intensity: f32,
+    ) -> Result<ObjectId, ThreeError> {
+        check_id(unsafe { super::createLight(light, color, intensity) })
+    }
+    /// Sets the distance and decay of a point or spot light.
+    pub fn set_light_params(
+        light_id: ObjectId,
+        distance: f32,
+        decay: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setLightParams(light_id.raw(), distance, decay) })
+    }
+    /// Sets the point a directional or spot light is aimed at.
+    pub fn set_light_target(light_id: ObjectId, x: f32, y: f32, z: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setLightTarget(light_id.raw(), x, y, z) })
+    }
+
+    pub fn set_material_color(
+        object_id: ObjectId,
+        color: impl Into<Color>,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setMaterialColor(object_id.raw(), color.into().as_i32()) })
+    }
+    /// Sets the opacity of the object's material.
+    ///
+    /// When `transparent` is `None`, transparency is enabled for any opacity below 1.0.
+    pub fn set_material_opacity(
+        object_id: ObjectId,
+        opacity: f32,
+        transparent: Option<bool>,
+    ) -> Result<(), ThreeError> {
+        let transparent = transparent.unwrap_or(opacity < 1.0);
+        check(unsafe { super::setMaterialOpacity(object_id.raw(), opacity, transparent as i32) })
+    }
+    /// Like [`set_material_opacity`], but only fades this object: if its material is shared
+    /// with other objects (e.g. after [`clone_object`]), it first gets its own copy, see
+    /// [`unshare_material`]. Transparency is enabled for any opacity below 1.0.
+    pub fn set_object_opacity(object_id: ObjectId, opacity: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setObjectOpacity(object_id.raw(), opacity) })
+    }
+    /// Gives the object its own copy of its material if any other object uses the same one,
+    /// so that later material changes only affect this object. Does nothing otherwise.
+    ///
+    /// Checking for sharing walks every object, and each copy is a separate material which
+    /// three.js has to compile a program for and can no longer batch with the original, so
+    /// prefer sharing materials between the many objects which look alike.
+    pub fn unshare_material(object_id: ObjectId) -> Result<(), ThreeError> {
+        check(unsafe { super::unshareMaterial(object_id.raw()) })
+    }
+
+    /// Sets metalness and roughness of a `MeshStandardMaterial`/`MeshPhysicalMaterial`.
+    /// Both values are clamped to `0.0..=1.0`.
+    pub fn set_material_pbr(
+        object_id: ObjectId,
+        metalness: f32,
+        roughness: f32,
+    ) -> Result<(), ThreeError> {
+        let metalness = metalness.clamp(0.0, 1.0);
+        let roughness = roughness.clamp(0.0, 1.0);
+        check(unsafe { super::setMaterialPBR(object_id.raw(), metalness, roughness) })
+    }
+    /// Sets how strongly a `MeshStandardMaterial`/`MeshPhysicalMaterial` reflects the
+    /// environment set with [`set_scene_environment`]. The default is 1.0, 0.0 disables
+    /// reflections and values above 1.0 over-brighten them for a stylized look. Negative
+    /// values are clamped to 0.0.
+    pub fn set_material_env_intensity(
+        object_id: ObjectId,
+        intensity: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setEnvMapIntensity(object_id.raw(), intensity.max(0.0)) })
+    }
+    pub fn set_material_emissive(
+        object_id: ObjectId,
+        color: impl Into<Color>,
+        intensity: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe {
+            super::setMaterialEmissive(object_id.raw(), color.into().as_i32(), intensity)
+        })
+    }
+
+    /// Renders the object's geometry as wireframe. Works with all mesh material classes;
+    /// it is a no-op for line and points materials.
+    pub fn set_wireframe(object_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setWireframe(object_id.raw(), flag(enabled)) })
+    }
+    /// Selects which faces of the object are rendered. Materials render [`Side::Front`] by
+    /// default.
+    ///
+    /// [`Side::Double`] turns off back-face culling, which makes planes and cloth visible from
+    /// behind but doubles the faces the GPU has to draw.
+    pub fn set_material_side(object_id: ObjectId, side: super::Side) -> Result<(), ThreeError> {
+        check(unsafe { super::setMaterialSide(object_id.raw(), side as i32) })
+    }
+    /// Whether the object is hidden behind objects closer to the camera. With the depth test
+    /// off and a high [`set_render_order`], an object always draws on top, e.g. for a HUD.
+    pub fn set_depth_test(object_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setDepthTest(object_id.raw(), flag(enabled)) })
+    }
+    /// Whether the object writes its depth, hiding whatever is drawn behind it afterwards.
+    ///
+    /// three.js sorts transparent objects back to front, but not the faces within one
+    /// object. A transparent mesh with [`Side::Double`](super::Side::Double) (see
+    /// [`set_material_side`] and [`set_material_opacity`]) can therefore hide its own back
+    /// faces, which looks wrong for glass. Turning depth writes off fixes that: the object
+    /// is still hidden by opaque objects in front of it, but no longer hides anything
+    /// itself. The price is that overlapping transparent objects may blend in the wrong
+    /// order, which is rarely visible at low opacity.
+    pub fn set_depth_write(object_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setDepthWrite(object_id.raw(), flag(enabled)) })
+    }
+    /// Objects with a higher order are drawn later. Opaque, depth-tested objects hide each
+    /// other regardless of the order, so it only matters among transparent objects and
+    /// objects with [`set_depth_test`] off.
+    pub fn set_render_order(object_id: ObjectId, order: i32) -> Result<(), ThreeError> {
+        check(unsafe { super::setRenderOrder(object_id.raw(), order) })
+    }
+    /// Shades every face with a single normal, for a low-poly look.
+    ///
+    /// The JS side recompiles the material (`needsUpdate`) and computes vertex normals if the
+    /// geometry has none, so toggling this is not free. It is a no-op for materials which
+    /// ignore normals, like `MeshBasicMaterial`.
+    pub fn set_flat_shading(object_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setFlatShading(object_id.raw(), flag(enabled)) })
+    }
+    /// Discards fragments whose alpha is below `threshold` (0.0 to 1.0), and 0.0 turns it
+    /// off. Meant for cutout textures set with [`set_material_map`], like grass or leaves.
+    ///
+    /// Unlike blending with [`set_material_opacity`] the kept fragments stay opaque, so
+    /// overlapping objects don't need to be sorted and never draw in the wrong order. The
+    /// edges are hard instead of smooth.
+    pub fn set_alpha_test(object_id: ObjectId, threshold: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setAlphaTest(object_id.raw(), threshold) })
+    }
+
+    /// Creates an empty transform node which can be used as a parent for other objects.
+    pub fn create_group() -> Result<ObjectId, ThreeError> {
+        check_id(unsafe { super::createGroup() })
+    }
+    /// Creates a level-of-detail node, which shows one of its levels depending on the distance
+    /// to the camera. Levels are added with [`add_lod_level`]; the node itself is added to the
+    /// scene like any other object.
+    pub fn create_lod() -> Result<ObjectId, ThreeError> {
+        check_id(unsafe { super::createLOD() })
+    }
+    /// Adds `object_id` as a level of the LOD node, shown from `distance` onwards until the
+    /// next level's distance. The level is picked for the active camera on every [`render`].
+    ///
+    /// The object becomes a child of the LOD node, so it should not also be added to the scene
+    /// or to another parent directly.
+    pub fn add_lod_level(
+        lod_id: ObjectId,
+        object_id: ObjectId,
+        distance: f32,
+    ) -> Result<(), ThreeError> {
+        if distance.is_nan() || distance < 0.0 {
+            return Err(ThreeError::InvalidArgument);
+        }
+        check(unsafe { super::addLODLevel(lod_id.raw(), object_id.raw(), distance) })
+    }
+    /// Attaches `child` to `parent`. The child's transform becomes relative to the parent.
+    ///
+    /// An object has at most one parent, so a child does not need to be added with
+    /// [`add_object_to_scene`]: it is rendered whenever its parent is part of the scene.
+    /// Calling [`add_object_to_scene`] on a child detaches it from its parent again.
+    pub fn add_child(parent: ObjectId, child: ObjectId) -> Result<(), ThreeError> {
+        check(unsafe { super::addChild(parent.raw(), child.raw()) })
+    }
+    pub fn remove_child(parent: ObjectId, child: ObjectId) -> Result<(), ThreeError> {
+        check(unsafe { super::removeChild(parent.raw(), child.raw()) })
+    }
+
+    /// Starts loading a texture from `url` and returns its handle, usable with [`create_sprite`].
+    ///
+    /// Loading is asynchronous: the id is valid right away, but objects using it render
+    /// blank until the texture is loaded. Use [`texture_is_ready`] to check.
+    pub fn load_texture(url: &str) -> Result<TextureHandle, ThreeError> {
+        check_value(unsafe { super::loadTexture(url.as_ptr(), url.len() as i32) })
+            .map(TextureHandle::new)
+    }
+    pub fn texture_is_ready(texture: TextureHandle) -> bool {
+        unsafe { super::textureIsReady(texture.raw()) != 0 }
+    }
+    /// Frees the texture on the GPU, e.g. when tearing down a level. The handle becomes
+    /// invalid: any later call using it returns [`ThreeError::UnknownObject`].
+    ///
+    /// Materials which still use the texture lose it and render with their plain color,
+    /// as if the map was never set. Sprites keep their own copy of the texture and are not
+    /// affected. Disposing a render target texture frees the whole render target.
+    pub fn dispose_texture(texture: TextureHandle) -> Result<(), ThreeError> {
+        check(unsafe { super::disposeTexture(texture.raw()) })
+    }
+    /// Sets the anisotropic filtering level of the texture, which keeps it sharp when seen at
+    /// grazing angles, e.g. on a ground plane. Levels above [`max_anisotropy`] are clamped to it.
+    ///
+    /// Textures start at level 1 (no anisotropic filtering). Higher levels cost more texture
+    /// lookups per pixel, so prefer them on textures which actually need it.
+    pub fn set_texture_anisotropy(texture: TextureHandle, level: u32) -> Result<(), ThreeError> {
+        let level = level.clamp(1, i32::MAX as u32) as i32;
+        check(unsafe { super::setTextureAnisotropy(texture.raw(), level) })
+    }
+    /// Highest anisotropic filtering level supported by the device, 1 if there is no support
+    /// or before initialization.
+    pub fn max_anisotropy() -> u32 {
+        unsafe { super::getMaxAnisotropy() }.max(1) as u32
+    }
+    /// Sets how the texture is wrapped horizontally (`mode_s`) and vertically (`mode_t`).
+    pub fn set_texture_wrap(
+        texture: TextureHandle,
+        mode_s: super::Wrap,
+        mode_t: super::Wrap,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setTextureWrap(texture.raw(), mode_s as i32, mode_t as i32) })
+    }
+    /// Sets how many times the texture is repeated across the surface, e.g. `(10.0, 10.0)`
+    /// for a tiled ground plane.
+    ///
+    /// Repeats above 1 only tile along axes wrapped with [`Wrap::Repeat`](super::Wrap::Repeat)
+    /// or [`Wrap::MirroredRepeat`](super::Wrap::MirroredRepeat), see [`set_texture_wrap`].
+    /// With the default [`Wrap::ClampToEdge`](super::Wrap::ClampToEdge) the edge pixels are
+    /// stretched instead.
+    pub fn set_texture_repeat(texture: TextureHandle, u: f32, v: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setTextureRepeat(texture.raw(), u, v) })
+    }
+
+    /// Sets the color map of the object's material.
+    ///
+    /// Passing a disposed texture returns an error and leaves the current map unchanged.
+    pub fn set_material_map(object_id: ObjectId, texture: TextureHandle) -> Result<(), ThreeError> {
+        check(unsafe { super::setMaterialMap(object_id.raw(), texture.raw()) })
+    }
+    /// Same as [`set_material_map`], for the normal map of PBR materials.
+    pub fn set_material_normal_map(
+        object_id: ObjectId,
+        texture: TextureHandle,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setMaterialNormalMap(object_id.raw(), texture.raw()) })
+    }
+    /// Same as [`set_material_map`], for the roughness map of `MeshStandardMaterial`.
+    pub fn set_material_roughness_map(
+        object_id: ObjectId,
+        texture: TextureHandle,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setMaterialRoughnessMap(object_id.raw(), texture.raw()) })
+    }
+    /// Sets the matcap texture of a [`MeshMatcapMaterial`](super::MaterialClass::MeshMatcapMaterial),
+    /// a sphere rendered in the wanted style. Other materials return
+    /// [`ThreeError::InvalidArgument`].
+    pub fn set_matcap_texture(
+        object_id: ObjectId,
+        texture: TextureHandle,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setMatcapTexture(object_id.raw(), texture.raw()) })
+    }
+
+    /// Enables linear fog between `near` and `far`.
+    ///
+    /// Use the same color for the fog and [`set_bg`] so that distant objects blend
+    /// seamlessly into the horizon.
+    pub fn set_fog(color: impl Into<Color>, near: f32, far: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setFog(color.into().as_i32(), near, far) })
+    }
+    /// Enables exponential squared fog. See [`set_fog`] regarding the background color.
+    pub fn set_fog_exp2(color: impl Into<Color>, density: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setFogExp2(color.into().as_i32(), density) })
+    }
+    pub fn clear_fog() -> Result<(), ThreeError> {
+        check(unsafe { super::clearFog() })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    type FrameCallback = Box<dyn FnMut(f32) -> bool>;
+
+    /// Drives the game loop from `requestAnimationFrame`.
+    ///
+    /// Every frame `update` is called with the time elapsed since the previous frame,
+    /// then the scene is rendered. Returning `false` from `update` stops the loop.
+    ///
+    /// Replaces a hand-rolled loop in which JS calls an exported function every frame:
+    ///
+    /// ```ignore
+    /// let obj = ctx::create_object(GeometryClass::BoxGeometry, MaterialClass::MeshBasicMaterial)?;
+    /// ctx::add_object_to_scene(obj)?;
+    /// let mut rot = 0.0;
+    /// ctx::run_loop(move |delta| {
+    ///     rot += delta;
+    ///     ctx::set_rotation(obj, rot, rot, 0.0).is_ok()
+    /// })?;
+    /// ```
+    ///
+    /// Errors from rendering are ignored, so that a single failed frame doesn't end the
+    /// loop; `update` can't see them.
+    ///
+    /// The JS side must be bound to the instance with `bindInstance` so that it can call
+    /// back into the exported `threejs4wasm_frame` function, otherwise this fails with
+    /// [`ThreeError::NotInitialized`] and `update` is dropped.
+    #[cfg(target_arch = "wasm32")]
+    pub fn run_loop<F: FnMut(f32) -> bool + 'static>(update: F) -> Result<(), ThreeError> {
+        let state = Box::into_raw(Box::<FrameCallback>::new(Box::new(update)));
+        let result = check(unsafe { super::requestFrame(state as *mut core::ffi::c_void) });
+        if result.is_err() {
+            // no frame was scheduled, so `threejs4wasm_frame` won't free it
+            drop(unsafe { Box::from_raw(state) });
+        }
+        result
+    }
+
+    /// Called by the JS side once per animation frame scheduled by [`run_loop`].
+    /// Returns 1 if another frame should be requested.
+    ///
+    /// # Safety
+    ///
+    /// `state` must be the pointer passed to `requestFrame` by [`run_loop`], and must not be
+    /// used again after this function returned 0.
+    #[cfg(target_arch = "wasm32")]
+    #[unsafe(no_mangle)]
+    pub unsafe extern "C" fn threejs4wasm_frame(state: *mut core::ffi::c_void) -> i32 {
+        let update = unsafe { &mut *(state as *mut FrameCallback) };
+        let keep_going = update(delta_time());
+        // see `run_loop`, there is no one to report the error to
+        let _ = render();
+        if !keep_going {
+            drop(unsafe { Box::from_raw(state as *mut FrameCallback) });
+            return 0;
+        }
+        1
+    }
+
+    /// Stops calling the [`run_loop`] callback (and rendering) until [`resume`], e.g. while
+    /// a pause menu is shown or the window is in the background. Does nothing if already
+    /// paused. [`elapsed_time`] keeps running.
+    pub fn pause() -> Result<(), ThreeError> {
+        check(unsafe { super::pauseLoop() })
+    }
+    /// Restarts a loop stopped with [`pause`].
+    ///
+    /// The first frame after resuming gets a delta of 0 (also from [`delta_time`]) instead of
+    /// the whole time spent paused, so that objects moved by `speed * delta` don't teleport
+    /// and physics doesn't have to catch up. Animations continue where they stopped.
+    pub fn resume() -> Result<(), ThreeError> {
+        check(unsafe { super::resumeLoop() })
+    }
+    pub fn is_paused() -> bool {
+        unsafe { super::isPaused() != 0 }
+    }
+
+    /// Casts a ray from the camera through a point given in normalized device coordinates
+    /// (`-1..1` on both axes, `(-1, -1)` is the bottom-left corner, y points up) and returns
+    /// the closest object hit, if any.
+    pub fn raycast_from_camera(ndc: (f32, f32)) -> Option<ObjectId> {
+        check_id(unsafe { super::raycastFromCamera(ndc.0, ndc.1) }).ok()
+    }
+    /// Returns the world-space point where the last [`raycast_from_camera`] hit the object,
+    /// or `None` if the ray did not hit it.
+    pub fn raycast_point(object_id: ObjectId) -> Option<Vec3> {
+        let mut out = [0.0f32; 3];
+        check(unsafe { super::raycastPoint(object_id.raw(), out.as_mut_ptr()) }).ok()?;
+        Some(Vec3::new(out[0], out[1], out[2]))
+    }
+    /// Projects a world-space point through the active camera to pixels on the canvas,
+    /// measured from its top-left corner like [`get_mouse_position`]. Points outside the view
+    /// get coordinates outside the canvas.
+    ///
+    /// `p` is in the coordinates chosen with [`set_coordinate_system`], while the pixels are
+    /// always counted downwards from the top.
+    ///
+    /// Returns `None` for points behind the camera (or before `init`). The result is only
+    /// valid until the camera moves, so call it every frame when tracking an object.
+    pub fn world_to_screen(p: Vec3) -> Option<super::TwoI16> {
+        let p = coordinate_system().convert_point(p);
+        let mut out = [0.0f32; 2];
+        check(unsafe { super::worldToScreen(p.x, p.y, p.z, out.as_mut_ptr()) }).ok()?;
+        Some(super::TwoI16 {
+            x: out[0] as i16,
+            y: out[1] as i16,
+        })
+    }
+    /// The opposite of [`world_to_screen`]: the world-space point `depth` units away from the
+    /// active camera along the ray through the pixel `screen`, in the same corner-based
+    /// pixel coordinates. For an orthographic camera the ray starts on its near plane.
+    pub fn screen_to_world(screen: (f32, f32), depth: f32) -> Result<Vec3, ThreeError> {
+        let mut out = [0.0f32; 3];
+        check(unsafe { super::screenToWorld(screen.0, screen.1, depth, out.as_mut_ptr()) })?;
+        Ok(coordinate_system().convert_point(Vec3::new(out[0], out[1], out[2])))
+    }
+
+    /// Turns on the shadow map of the renderer.
+    ///
+    /// A shadow is only visible when all of these hold: shadows are enabled, a directional or
+    /// spot light casts shadows ([`set_light_cast_shadow`]), an object casts a shadow
+    /// ([`set_cast_shadow`]) and another object receives it ([`set_receive_shadow`]).
+    pub fn enable_shadows() -> Result<(), ThreeError> {
+        check(unsafe { super::enableShadows() })
+    }
+    pub fn set_cast_shadow(object_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setCastShadow(object_id.raw(), flag(enabled)) })
+    }
+    pub fn set_receive_shadow(object_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setReceiveShadow(object_id.raw(), flag(enabled)) })
+    }
+    /// Makes a directional, spot or point light cast shadows. See [`enable_shadows`].
+    pub fn set_light_cast_shadow(light_id: ObjectId, enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setLightCastShadow(light_id.raw(), flag(enabled)) })
+    }
+
+    /// Creates a mesh drawing `count` instances of the same geometry and material in a single
+    /// draw call. `count` is the maximum number of instances of the mesh and can't be changed later.
+    pub fn create_instanced_mesh(
+        geometry: super::GeometryClass,
+        material: super::MaterialClass,
+        count: u32,
+    ) -> Result<ObjectId, ThreeError> {
+        if !super::is_valid_combo(geometry, material) {
+            return Err(ThreeError::InvalidArgument);
+        }
+        let count = i32::try_from(count).map_err(|_| ThreeError::InvalidArgument)?;
+        check_id(unsafe { super::createInstancedMesh(geometry, material, count) })
+    }
+    /// Sets the transform of a single instance, relative to the instanced mesh.
+    /// Changes only take effect after calling [`update_instances`].
+    pub fn set_instance_transform(
+        mesh_id: ObjectId,
+        index: u32,
+        pos: Vec3,
+        rot: Vec3,
+        scale: Vec3,
+    ) -> Result<(), ThreeError> {
+        let index = i32::try_from(index).map_err(|_| ThreeError::InvalidArgument)?;
+        check(unsafe {
+            super::setInstanceTransform(
+                mesh_id.raw(),
+                index,
+                pos.x,
+                pos.y,
+                pos.z,
+                rot.x,
+                rot.y,
+                rot.z,
+                scale.x,
+                scale.y,
+                scale.z,
+            )
+        })
+    }
+    /// Uploads the instance transforms set with [`set_instance_transform`] to the GPU.
+    pub fn update_instances(mesh_id: ObjectId) -> Result<(), ThreeError> {
+        check(unsafe { super::updateInstances(mesh_id.raw()) })
+    }
+
+    /// Starts loading a sound from `url` and returns its id.
+    ///
+    /// Like [`load_texture`], loading is asynchronous: playing a sound which is still
+    /// loading is silently dropped.
+    pub fn load_sound(url: &str) -> Result<i32, ThreeError> {
+        check_value(unsafe { super::loadSound(url.as_ptr(), url.len() as i32) })
+    }
+    pub fn play_sound(sound_id: i32, volume: f32, looping: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::playSound(sound_id, volume, flag(looping)) })
+    }
+    /// Plays the sound from a point in the world, attenuated by its distance to the camera.
+    pub fn play_sound_at(
+        sound_id: i32,
+        pos: Vec3,
+        volume: f32,
+        looping: bool,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::playSoundAt(sound_id, pos.x, pos.y, pos.z, volume, flag(looping)) })
+    }
+    /// Stops the sound, both its regular and positional playback.
+    pub fn stop_sound(sound_id: i32) -> Result<(), ThreeError> {
+        check(unsafe { super::stopSound(sound_id) })
+    }
+
+    /// Creates a point cloud with one point per position. It is not added to scene by default.
+    ///
+    /// The positions are uploaded as a flat `f32` buffer of interleaved `x, y, z` triples.
+    /// `material` must be [`MaterialClass::PointsMaterial`](super::MaterialClass::PointsMaterial).
+    pub fn create_points(
+        positions: &[Vec3],
+        material: super::MaterialClass,
+    ) -> Result<ObjectId, ThreeError> {
+        if material != super::MaterialClass::PointsMaterial {
+            return Err(ThreeError::InvalidArgument);
+        }
+        let buf = crate::math::flatten(positions);
+        let len = i32::try_from(buf.len()).map_err(|_| ThreeError::InvalidArgument)?;
+        check_id(unsafe { super::createPoints(buf.as_ptr(), len, material) })
+    }
+    /// Sets the size of the points of a point cloud. The size is in world units while size
+    /// attenuation is on (the default, see [`set_points_size_attenuation`]) and in pixels
+    /// while it is off.
+    pub fn set_points_size(object_id: ObjectId, size: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setPointsSize(object_id.raw(), size) })
+    }
+    /// Whether points shrink with their distance to the camera.
+    pub fn set_points_size_attenuation(
+        object_id: ObjectId,
+        enabled: bool,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setPointsSizeAttenuation(object_id.raw(), flag(enabled)) })
+    }
+
+    /// Creates a line going through `points`. It is not added to scene by default.
+    ///
+    /// Same buffer layout as [`create_points`]. `material` must be
+    /// [`MaterialClass::LineBasicMaterial`](super::MaterialClass::LineBasicMaterial) or
+    /// [`MaterialClass::LineDashedMaterial`](super::MaterialClass::LineDashedMaterial).
+    pub fn create_line(
+        points: &[Vec3],
+        material: super::MaterialClass,
+    ) -> Result<ObjectId, ThreeError> {
+        if !matches!(
+            material,
+            super::MaterialClass::LineBasicMaterial | super::MaterialClass::LineDashedMaterial
+        ) {
+            return Err(ThreeError::InvalidArgument);
+        }
+        let buf = crate::math::flatten(points);
+        let len = i32::try_from(buf.len()).map_err(|_| ThreeError::InvalidArgument)?;
+        check_id(unsafe { super::createLine(buf.as_ptr(), len, material) })
+    }
+    /// Creates a line `width` pixels wide going through `points`, drawn with three.js's
+    /// `Line2`, as plain lines are always 1 pixel wide in WebGL. It is not added to scene by
+    /// default.
+    ///
+    /// The width depends on the resolution of the canvas, which the JS side updates in
+    /// [`set_renderer_size`]. Call [`on_resize`] when the canvas changes size.
+    pub fn create_line2(
+        points: &[Vec3],
+        width: f32,
+        color: impl Into<Color>,
+    ) -> Result<ObjectId, ThreeError> {
+        let buf = crate::math::flatten(points);
+        let len = i32::try_from(buf.len()).map_err(|_| ThreeError::InvalidArgument)?;
+        check_id(unsafe { super::createLine2(buf.as_ptr(), len, width, color.into().as_i32()) })
+    }
+    /// Sets the color of a line made by [`create_line`] or [`create_line2`].
+    pub fn set_line_color(object_id: ObjectId, color: impl Into<Color>) -> Result<(), ThreeError> {
+        check(unsafe { super::setLineColor(object_id.raw(), color.into().as_i32()) })
+    }
+    /// Sets the length of the dashes and of the gaps between them of a dashed line.
+    pub fn set_dash_params(
+        object_id: ObjectId,
+        dash_size: f32,
+        gap_size: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setDashParams(object_id.raw(), dash_size, gap_size) })
+    }
+
+    /// Returns the world-space bounding box of the object and its children.
+    ///
+    /// The box is recomputed on every call, so it reflects the current transform.
+    pub fn bounding_box(object_id: ObjectId) -> Result<Aabb, ThreeError> {
+        let mut out = [0.0f32; 6];
+        check(unsafe { super::getBoundingBox(object_id.raw(), out.as_mut_ptr()) })?;
+        Ok(Aabb::new(
+            Vec3::new(out[0], out[1], out[2]),
+            Vec3::new(out[3], out[4], out[5]),
+        ))
+    }
+    /// Returns the center and radius of a world-space sphere enclosing the object.
+    /// Like [`bounding_box`], it is recomputed on every call.
+    pub fn bounding_sphere(object_id: ObjectId) -> Result<(Vec3, f32), ThreeError> {
+        let mut out = [0.0f32; 4];
+        check(unsafe { super::getBoundingSphere(object_id.raw(), out.as_mut_ptr()) })?;
+        Ok((Vec3::new(out[0], out[1], out[2]), out[3]))
+    }
+    /// Whether the object's [`bounding_sphere`] is at least partly inside the view of the
+    /// active camera, e.g. to skip updating enemies which are off screen. Objects without
+    /// geometry are tested by their position.
+    ///
+    /// Errors read as `false` too: an unknown (e.g. disposed) object and a camera used
+    /// before `init` both report the object as off screen.
+    ///
+    /// The test uses the camera and object as they are at the time of the call, so call it
+    /// after moving the camera for the frame. It doesn't check whether something else blocks
+    /// the view of the object.
+    pub fn is_in_frustum(object_id: ObjectId) -> bool {
+        unsafe { super::isInFrustum(object_id.raw()) > 0 }
+    }
+
+    /// Starts loading a GLTF/GLB model from `url` and returns the id of its root node, which
+    /// can be added to the scene right away with [`add_object_to_scene`].
+    ///
+    /// Loading is asynchronous: the root stays empty until the model arrives, see
+    /// [`model_is_ready`]. Transforms set on the returned id apply to the model's root node.
+    pub fn load_model(url: &str) -> Result<ObjectId, ThreeError> {
+        check_id(unsafe { super::loadModel(url.as_ptr(), url.len() as i32) })
+    }
+    pub fn model_is_ready(model_id: ObjectId) -> bool {
+        unsafe { super::modelIsReady(model_id.raw()) != 0 }
+    }
+    /// Names of the animation clips embedded in the model, in the order used by the JS side.
+    /// Empty until the model is loaded.
+    pub fn model_animation_names(model_id: ObjectId) -> Result<Vec<String>, ThreeError> {
+        let count = check_value(unsafe { super::getModelAnimationCount(model_id.raw()) })?;
+        let mut names = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let mut buf = vec![0u8; 64];
+            let mut len = check_value(unsafe {
+                super::getModelAnimationName(
+                    model_id.raw(),
+                    index,
+                    buf.as_mut_ptr(),
+                    buf.len() as i32,
+                )
+            })? as usize;
+            if len > buf.len() {
+                // the name did not fit, ask again with the full length
+                buf.resize(len, 0);
+                len = check_value(unsafe {
+                    super::getModelAnimationName(
+                        model_id.raw(),
+                        index,
+                        buf.as_mut_ptr(),
+                        buf.len() as i32,
+                    )
+                })? as usize;
+            }
+            buf.truncate(len);
+            names.push(String::from_utf8(buf).map_err(|_| ThreeError::InvalidArgument)?);
+        }
+        Ok(names)
+    }
+
+    /// Plays the animation clip with the given index (see [`model_animation_names`]),
+    /// replacing the one currently playing. Clips advance on every [`render`].
+    ///
+    /// Calling this on a model which is not loaded yet is a no-op.
+    pub fn play_animation(
+        model_id: ObjectId,
+        clip_index: i32,
+        looping: bool,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::playAnimation(model_id.raw(), clip_index, flag(looping)) })
+    }
+    pub fn stop_animation(model_id: ObjectId) -> Result<(), ThreeError> {
+        check(unsafe { super::stopAnimation(model_id.raw()) })
+    }
+    /// Jumps the playing animation of the model to `t` seconds.
+    pub fn set_animation_time(model_id: ObjectId, t: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setAnimationTime(model_id.raw(), t) })
+    }
+    /// Duration of the clip in seconds.
+    pub fn animation_duration(model_id: ObjectId, clip_index: i32) -> Result<f32, ThreeError> {
+        let duration = unsafe { super::getAnimationDuration(model_id.raw(), clip_index) };
+        if duration < 0.0 {
+            Err(ThreeError::from_code(duration as i32))
+        } else {
+            Ok(duration)
+        }
+    }
+    /// Sets the influence of the morph target with the given index on every mesh of the
+    /// model, clamped to `0.0..=1.0`.
+    ///
+    /// This only applies to models loaded with morph data (e.g. glTF blend shapes);
+    /// see [`morph_target_count`].
+    pub fn set_morph_weight(model_id: ObjectId, index: u32, weight: f32) -> Result<(), ThreeError> {
+        let index = i32::try_from(index).map_err(|_| ThreeError::InvalidArgument)?;
+        check(unsafe { super::setMorphWeight(model_id.raw(), index, weight.clamp(0.0, 1.0)) })
+    }
+    /// Number of morph targets of the model, or 0 if it has none or is not loaded yet.
+    pub fn morph_target_count(model_id: ObjectId) -> Result<u32, ThreeError> {
+        check_value(unsafe { super::getMorphTargetCount(model_id.raw()) }).map(|n| n as u32)
+    }
+
+    /// Size of the element the renderer was attached to by the JS `init`, in pixels.
+    /// Returns `(0, 0)` before initialization.
+    pub fn canvas_size() -> (i32, i32) {
+        let mut out = [0i32; 2];
+        unsafe { super::getCanvasSize(out.as_mut_ptr()) };
+        (out[0], out[1])
+    }
+    /// Encodes the current contents of the canvas as a PNG file, e.g. to offer a screenshot
+    /// for download.
+    ///
+    /// The browser clears the canvas once a frame is shown, unless the renderer was created
+    /// with `preserveDrawingBuffer`. Without it, call this right after your own [`render`],
+    /// in the same frame; called anywhere else it captures an empty image. Encoding a large
+    /// canvas takes a while, so don't do it every frame.
+    pub fn capture_frame() -> Result<Vec<u8>, ThreeError> {
+        let mut buf = vec![0u8; 1 << 20];
+        loop {
+            let len = check_value(unsafe {
+                super::captureFrame(
+                    buf.as_mut_ptr(),
+                    i32::try_from(buf.len()).unwrap_or(i32::MAX),
+                )
+            })? as usize;
+            if len <= buf.len() {
+                buf.truncate(len);
+                return Ok(buf);
+            }
+            // the image did not fit, ask again with the full length
+            buf.resize(len, 0);
+        }
+    }
+    pub fn set_renderer_size(width: i32, height: i32) -> Result<(), ThreeError> {
+        check(unsafe { super::setRendererSize(width, height) })
+    }
+    /// Resizes the renderer to [`canvas_size`] and updates the camera aspect ratio to match.
+    ///
+    /// Call this at the top of each frame or in response to a resize event, otherwise the
+    /// picture stretches when the canvas changes size. Does nothing while the canvas has no area.
+    pub fn on_resize() -> Result<(), ThreeError> {
+        let (width, height) = canvas_size();
+        if width <= 0 || height <= 0 {
+            return Ok(());
+        }
+        set_renderer_size(width, height)?;
+        set_camera_aspect(width as f32 / height as f32)
+    }
+
+    /// Creates a perspective camera in addition to [`CameraId::DEFAULT`] and returns its id.
+    pub fn create_camera() -> Result<CameraId, ThreeError> {
+        check_value(unsafe { super::createCamera() }).map(CameraId::new)
+    }
+    /// Creates an off-screen render target of `width` x `height` pixels and returns its
+    /// texture, usable with [`set_material_map`] like a loaded texture.
+    pub fn create_render_target(width: i32, height: i32) -> Result<TextureHandle, ThreeError> {
+        check_value(unsafe { super::createRenderTarget(width, height) }).map(TextureHandle::new)
+    }
+    /// Renders the scene from `camera_id` into the render target. The texture shows the
+    /// result on the next [`render`].
+    pub fn render_to_target(target: TextureHandle, camera_id: CameraId) -> Result<(), ThreeError> {
+        check(unsafe { super::renderToTarget(target.raw(), camera_id.raw()) })
+    }
+
+    /// Starts loading a cube map from six face images and returns its texture.
+    ///
+    /// Faces are ordered like `THREE.CubeTextureLoader` expects them: +x, -x, +y, -y, +z, -z
+    /// (`px`, `nx`, `py`, `ny`, `pz`, `nz`). Like [`load_texture`], loading is asynchronous.
+    pub fn load_cube_map(faces: [&str; 6]) -> Result<TextureHandle, ThreeError> {
+        if faces.iter().any(|face| face.contains('\n')) {
+            return Err(ThreeError::InvalidArgument);
+        }
+        let urls = faces.join("\n");
+        check_value(unsafe { super::loadCubeMap(urls.as_ptr(), urls.len() as i32) })
+            .map(TextureHandle::new)
+    }
+    /// Shows the texture (usually a cube map from [`load_cube_map`]) as the scene background,
+    /// replacing the color set with [`set_bg`].
+    pub fn set_scene_background(texture: TextureHandle) -> Result<(), ThreeError> {
+        check(unsafe { super::setSceneBackground(texture.raw()) })
+    }
+    /// Uses the cube map as the environment map of every PBR material in the scene, for
+    /// reflections and image-based lighting.
+    pub fn set_scene_environment(texture: TextureHandle) -> Result<(), ThreeError> {
+        check(unsafe { super::setSceneEnvironment(texture.raw()) })
+    }
+
+    /// Creates a square grid of `size` x `size` on the XZ plane, split into `divisions`
+    /// cells along each side. It is not added to scene by default.
+    pub fn create_grid(
+        size: f32,
+        divisions: u32,
+        color: impl Into<Color>,
+    ) -> Result<ObjectId, ThreeError> {
+        let divisions = i32::try_from(divisions).map_err(|_| ThreeError::InvalidArgument)?;
+        check_id(unsafe { super::createGrid(size, divisions, color.into().as_i32()) })
+    }
+    /// Creates the x (red), y (green) and z (blue) axes gizmo, with lines of length `size`.
+    /// It is not added to scene by default.
+    pub fn create_axes_helper(size: f32) -> Result<ObjectId, ThreeError> {
+        check_id(unsafe { super::createAxesHelper(size) })
+    }
+
+    /// Sets the ratio of drawing buffer pixels to CSS pixels. Pass `window.devicePixelRatio`
+    /// (from the JS side) to get a sharp picture on high-DPI displays.
+    pub fn set_pixel_ratio(ratio: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setPixelRatio(ratio) })
+    }
+    /// Turns antialiasing on or off. Antialiasing is on by default.
+    ///
+    /// WebGL can only choose antialiasing when the context is created, so the JS side
+    /// replaces the renderer and its canvas. The change is visible from the next frame.
+    pub fn set_antialias(enabled: bool) -> Result<(), ThreeError> {
+        check(unsafe { super::setAntialias(flag(enabled)) })
+    }
+    /// Sets the tone mapping of the renderer together with its exposure (1.0 is neutral).
+    pub fn set_tone_mapping(mode: super::ToneMapping, exposure: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setToneMapping(mode as i32, exposure) })
+    }
+    /// Turns screen-space ambient occlusion on or off. `radius` is in world units and
+    /// `intensity` goes from 0.0 (no darkening) to 1.0 (full occlusion).
+    ///
+    /// While it is on, [`render`] goes through a post-processing composer instead of
+    /// drawing straight to the canvas, which costs a few extra full-screen passes per
+    /// frame. On devices which can't render to float textures this is a no-op: the JS side
+    /// logs a warning and keeps the plain renderer.
+    pub fn set_ssao(enabled: bool, radius: f32, intensity: f32) -> Result<(), ThreeError> {
+        check(unsafe { super::setSSAO(flag(enabled), radius, intensity) })
+    }
+    /// Turns bloom on or off: pixels brighter than `threshold` (0.0 to 1.0) glow into their
+    /// surroundings with the given `strength`, spreading further with a bigger `radius`
+    /// (0.0 to 1.0). Combined with [`set_material_emissive`] this makes objects glow.
+    ///
+    /// Like [`set_ssao`] this makes [`render`] go through a post-processing composer. Its
+    /// buffers follow [`set_pixel_ratio`], so a high pixel ratio makes bloom noticeably more
+    /// expensive. Unsupported devices keep the plain renderer with a warning.
+    pub fn set_bloom(
+        enabled: bool,
+        strength: f32,
+        radius: f32,
+        threshold: f32,
+    ) -> Result<(), ThreeError> {
+        check(unsafe { super::setBloom(flag(enabled), strength, radius, threshold) })
+    }
+
+    /// Creates a sprite showing `text`, `size` world units tall. It is not added to scene by
+    /// default.
+    ///
+    /// Like every sprite it always faces the camera (billboarding).
+    pub fn create_text_sprite(
+        text: &str,
+        size: f32,
+        color: impl Into<Color>,
+    ) -> Result<ObjectId, ThreeError> {
+        check_id(unsafe {
+            super::createTextSprite(
+                text.as_ptr(),
+                text.len() as i32,
+                size,
+                color.into().as_i32(),
+            )
+        })
+    }
+    /// Replaces the text of a sprite made by [`create_text_sprite`], keeping its height and color.
+    pub fn update_text_sprite(object_id: ObjectId, text: &str) -> Result<(), ThreeError> {
+        check(unsafe { super::updateTextSprite(object_id.raw(), text.as_ptr(), text.len() as i32) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use super::*;
+
+    fn assert_class_ids_round_trip<T: ClassId + PartialEq + core::fmt::Debug>() {
+        for class in T::ALL {
+            assert_eq!(T::from_i32(class.as_i32()), Some(*class));
+        }
+        assert_eq!(T::from_i32(0), None);
+        assert_eq!(T::from_i32(-1), None);
+    }
+
+    #[test]
+    fn class_ids_round_trip() {
+        assert_class_ids_round_trip::<MaterialClass>();
+        assert_class_ids_round_trip::<GeometryClass>();
+        assert_class_ids_round_trip::<LightClass>();
+        assert_eq!(
+            MaterialClass::from_i32(GeometryClass::BoxGeometry as i32),
+            None
+        );
+        assert_eq!(
+            LightClass::from_i32(MaterialClass::MeshBasicMaterial as i32),
+            None
+        );
+        assert_eq!(
+            MaterialClass::from_i32(1016),
+            Some(MaterialClass::SpriteMaterial)
+        );
+    }
+
+    #[test]
+    fn sprite_material_is_not_for_meshes() {
+        let geometry = GeometryClass::SphereGeometry;
+        assert!(is_valid_combo(geometry, MaterialClass::MeshMatcapMaterial));
+        assert!(is_valid_combo(geometry, MaterialClass::ShadowMaterial));
+        assert!(!is_valid_combo(geometry, MaterialClass::SpriteMaterial));
+    }
+
+    #[test]
+    fn movement_vector_is_normalized() {
+        assert_eq!(KeysSet(0).movement_vector(), Vec3::ZERO);
+        assert_eq!(
+            KeysSet(KeysSet::W | KeysSet::S).movement_vector(),
+            Vec3::ZERO
+        );
+        assert_eq!(
+            KeysSet(KeysSet::W).movement_vector(),
+            Vec3::new(0.0, 0.0, -1.0)
+        );
+
+        let diagonal = KeysSet(KeysSet::W | KeysSet::D).movement_vector();
+        assert!((diagonal.length() - 1.0).abs() < 1e-6);
+        assert!(diagonal.x > 0.0 && diagonal.z < 0.0);
+
+        let mapping = MovementMapping {
+            forward: Vec3::Z,
+            right: Vec3::new(-1.0, 0.0, 0.0),
+        };
+        let diagonal = KeysSet(KeysSet::S | KeysSet::A).movement_vector_with(&mapping);
+        assert!((diagonal.length() - 1.0).abs() < 1e-6);
+        assert!(diagonal.x > 0.0 && diagonal.z < 0.0);
+    }
+
+    #[test]
+    fn keys_set_iterates_pressed_keys() {
+        let set = KeysSet(KeysSet::A | KeysSet::W);
+        assert_eq!(set.keys().collect::<Vec<_>>(), vec![Key::A, Key::W]);
+        assert_eq!(KeysSet(0).into_iter().count(), 0);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn create_object_rejects_line_and_points_materials() {
+        assert!(
+            ctx::create_object(
+                GeometryClass::SphereGeometry,
+                MaterialClass::MeshStandardMaterial
+            )
+            .is_ok()
+        );
+        assert_eq!(
+            ctx::create_object(
+                GeometryClass::SphereGeometry,
+                MaterialClass::LineBasicMaterial
+            ),
+            Err(ThreeError::InvalidArgument)
+        );
+        assert_eq!(
+            ctx::create_object(GeometryClass::BoxGeometry, MaterialClass::PointsMaterial),
+            Err(ThreeError::InvalidArgument)
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn set_wireframe_passes_valid_flag() {
+        let id = ctx::create_object(GeometryClass::BoxGeometry, MaterialClass::MeshBasicMaterial)
+            .unwrap();
+        test::take_calls();
+        ctx::set_wireframe(id, true).unwrap();
+        ctx::set_wireframe(id, false).unwrap();
+        assert_eq!(
+            test::take_calls(),
+            vec![
+                test::Call::SetWireframe {
+                    object_id: id.raw(),
+                    enabled: 1
+                },
+                test::Call::SetWireframe {
+                    object_id: id.raw(),
+                    enabled: 0
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn recorder_logs_calls_and_counts_ids_up() {
+        test::set_next_id(7);
+        let a = ctx::create_object(GeometryClass::BoxGeometry, MaterialClass::MeshBasicMaterial)
+            .unwrap();
+        let b = ctx::create_group().unwrap();
+        assert_eq!((a.raw(), b.raw()), (7, 8));
+
+        test::take_calls();
+        ctx::set_position(a, -1.0, 0.0, 0.0).unwrap();
+        assert_eq!(
+            test::take_calls(),
+            vec![test::Call::SetPosition {
+                object_id: 7,
+                x: -1.0,
+                y: 0.0,
+                z: 0.0
+            }]
+        );
+        assert!(test::take_calls().is_empty());
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn cameras_are_passed_by_id() {
+        test::set_next_id(2);
+        let camera = ctx::create_camera().unwrap();
+        assert_ne!(camera, CameraId::DEFAULT);
+
+        test::take_calls();
+        ctx::set_active_camera(camera).unwrap();
+        ctx::set_active_camera(CameraId::DEFAULT).unwrap();
+        assert_eq!(
+            test::take_calls(),
+            vec![
+                test::Call::SetActiveCamera { camera_id: 2 },
+                test::Call::SetActiveCamera { camera_id: 0 },
+            ]
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn set_transform_is_a_single_call() {
+        let transform = Transform {
+            position: Vec3::new(1.0, 2.0, 3.0),
+            rotation: Vec3::new(0.1, 0.2, 0.3),
+            scale: Vec3::new(4.0, 5.0, 6.0),
+        };
+
+        test::take_calls();
+        ctx::set_transform(ObjectId(9), &transform).unwrap();
+        assert_eq!(
+            test::take_calls(),
+            vec![test::Call::SetTransform {
+                object_id: 9,
+                px: 1.0,
+                py: 2.0,
+                pz: 3.0,
+                rx: 0.1,
+                ry: 0.2,
+                rz: 0.3,
+                sx: 4.0,
+                sy: 5.0,
+                sz: 6.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn layers_intersect_on_shared_bits() {
+        let hud = Layers::layer(3);
+        let camera = Layers::default().with(3);
+        assert!(camera.contains(0) && camera.contains(3));
+        assert!(hud.intersects(&camera));
+        assert!(!hud.intersects(&Layers::default()));
+        assert_eq!(Layers::layer(32), Layers(Layers::NONE));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn vertex_colors_are_sent_as_flat_rgb() {
+        test::set_next_id(3);
+        let colors = [Color::RED, Color::rgb(0, 51, 255)];
+        let rgb: Vec<f32> = colors.iter().flat_map(|color| color.to_rgb_f32()).collect();
+        assert_eq!(rgb, vec![1.0, 0.0, 0.0, 0.0, 0.2, 1.0]);
+
+        test::take_calls();
+        ctx::create_geometry_with_colors(
+            GeometryClass::PlaneGeometry,
+            MaterialClass::MeshBasicMaterial,
+            &colors,
+        )
+        .unwrap();
+        let calls = test::take_calls();
+        assert!(matches!(
+            calls[1],
+            test::Call::SetVertexColors {
+                object_id: 3,
+                len: 6,
+                ..
+            }
+        ));
+        assert_eq!(
+            calls[2],
+            test::Call::SetVertexColorsEnabled {
+                object_id: 3,
+                enabled: 1
+            }
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn render_stats_fps_from_frame_time() {
+        let stats = ctx::render_stats();
+        assert_eq!((stats.draw_calls, stats.triangles), (12, 3_456));
+        assert!((stats.fps() - 60.0).abs() < 0.01);
+        assert_eq!(RenderStats::default().fps(), 0.0);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn events_are_polled_in_order() {
+        let texture = TextureHandle::new(5);
+        test::push_event(Event::WindowBlur);
+        test::push_event(Event::TextureLoaded(texture));
+        test::push_event(Event::SoundFinished(2));
+        assert_eq!(ctx::poll_event(), Some(Event::WindowBlur));
+        assert_eq!(ctx::poll_event(), Some(Event::TextureLoaded(texture)));
+        assert_eq!(ctx::poll_event(), Some(Event::SoundFinished(2)));
+        assert_eq!(ctx::poll_event(), None);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn morph_weight_is_clamped() {
+        let model = ObjectId(4);
+        test::take_calls();
+        ctx::set_morph_weight(model, 2, 1.5).unwrap();
+        ctx::set_morph_weight(model, 0, -0.5).unwrap();
+        assert_eq!(
+            test::take_calls(),
+            vec![
+                test::Call::SetMorphWeight {
+                    model_id: 4,
+                    index: 2,
+                    weight: 1.0
+                },
+                test::Call::SetMorphWeight {
+                    model_id: 4,
+                    index: 0,
+                    weight: 0.0
+                },
+            ]
+        );
+        assert_eq!(
+            ctx::set_morph_weight(model, u32::MAX, 0.5),
+            Err(ThreeError::InvalidArgument)
+        );
+    }
+}
+
+/// Builds the crate without default features, i.e. with `ffi` off and only `core` (and once
+/// more with `alloc`), so that breaking the `no_std` types shows up in a plain `cargo test`.
+/// Warnings are denied too, since code only used behind `ffi` shows up as dead code here.
+#[cfg(test)]
+mod no_std_check {
+    use std::process::Command;
+
+    #[test]
+    fn builds_without_ffi() {
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        for features in ["", "alloc"] {
+            let output = Command::new(env!("CARGO"))
+                .args([
+                    "build",
+                    "--lib",
+                    "--no-default-features",
+                    "--features",
+                    features,
+                ])
+                .arg("--target-dir")
+                .arg(std::format!("{manifest_dir}/target/no_std_check"))
+                .env("RUSTFLAGS", "-D warnings")
+                .current_dir(manifest_dir)
+                .output()
+                .expect("failed to run cargo");
+            assert!(
+                output.status.success(),
+                "features [{features}]: {}",
+                std::string::String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
 }
@@ -0,0 +1,749 @@
+use core::ops::{Add, Mul, Sub};
+
+use crate::float;
+
+/// 3D vector with the same memory layout as three floats on the JS side.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3::new(0.0, 0.0, 0.0);
+    pub const ONE: Vec3 = Vec3::new(1.0, 1.0, 1.0);
+    pub const X: Vec3 = Vec3::new(1.0, 0.0, 0.0);
+    pub const Y: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+    pub const Z: Vec3 = Vec3::new(0.0, 0.0, 1.0);
+
+    #[inline(always)]
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    #[inline(always)]
+    pub fn dot(self, other: Vec3) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Cross product, following the right-hand rule: `X.cross(Y) == Z`.
+    #[inline(always)]
+    pub fn cross(self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    #[inline(always)]
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    #[inline(always)]
+    pub fn length(self) -> f32 {
+        float::sqrt(self.length_squared())
+    }
+
+    /// Returns the vector scaled to unit length, or [`Vec3::ZERO`] (rather than NaNs) if its
+    /// length is zero.
+    pub fn normalize(self) -> Vec3 {
+        let len = self.length();
+        if len == 0.0 {
+            return Vec3::ZERO;
+        }
+        self * (1.0 / len)
+    }
+
+    #[inline(always)]
+    pub fn distance(self, other: Vec3) -> f32 {
+        (self - other).length()
+    }
+
+    /// Linear interpolation between `self` (`t = 0`) and `other` (`t = 1`).
+    pub fn lerp(self, other: Vec3, t: f32) -> Vec3 {
+        Vec3::new(
+            lerp(self.x, other.x, t),
+            lerp(self.y, other.y, t),
+            lerp(self.z, other.z, t),
+        )
+    }
+}
+
+/// Linear interpolation between `a` (`t = 0`) and `b` (`t = 1`). `t` is not clamped.
+#[inline(always)]
+pub fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    #[inline(always)]
+    fn add(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    #[inline(always)]
+    fn sub(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f32> for Vec3 {
+    type Output = Vec3;
+
+    #[inline(always)]
+    fn mul(self, rhs: f32) -> Vec3 {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// Axis-aligned bounding box.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    #[inline(always)]
+    pub const fn new(min: Vec3, max: Vec3) -> Self {
+        Aabb { min, max }
+    }
+
+    /// Whether the boxes overlap. Boxes which only touch count as intersecting.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+            && self.min.z <= other.max.z
+            && self.max.z >= other.min.z
+    }
+
+    /// Whether the point lies inside the box or on its boundary.
+    pub fn contains_point(&self, p: Vec3) -> bool {
+        p.x >= self.min.x
+            && p.x <= self.max.x
+            && p.y >= self.min.y
+            && p.y <= self.max.y
+            && p.z >= self.min.z
+            && p.z <= self.max.z
+    }
+}
+
+/// Views a slice of vectors as a flat buffer of interleaved `x, y, z` floats.
+pub fn flatten(points: &[Vec3]) -> &[f32] {
+    // SAFETY: `Vec3` is `repr(C)` with three `f32` fields and no padding
+    unsafe { core::slice::from_raw_parts(points.as_ptr() as *const f32, points.len() * 3) }
+}
+
+/// Rotation quaternion, in the same component order as `THREE.Quaternion`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Quat::IDENTITY
+    }
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat::new(0.0, 0.0, 0.0, 1.0);
+
+    #[inline(always)]
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Quat { x, y, z, w }
+    }
+
+    /// Rotation of `angle` radians around `axis`. The axis is expected to be normalized.
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let (s, c) = float::sin_cos(angle * 0.5);
+        Quat::new(axis.x * s, axis.y * s, axis.z * s, c)
+    }
+
+    #[inline(always)]
+    pub fn length(self) -> f32 {
+        float::sqrt(self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w)
+    }
+
+    #[inline(always)]
+    pub fn dot(self, other: Quat) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Spherical interpolation between two unit quaternions, rotating at constant speed from
+    /// `self` (`t = 0`) to `other` (`t = 1`) along the shorter path.
+    pub fn slerp(self, other: Quat, t: f32) -> Quat {
+        // q and -q are the same rotation, pick the one closer to `self`
+        let (other, cos) = match self.dot(other) {
+            d if d < 0.0 => (Quat::new(-other.x, -other.y, -other.z, -other.w), -d),
+            d => (other, d),
+        };
+
+        let (a, b) = if cos > 0.9995 {
+            // nearly the same rotation, sin(angle) is too small to divide by
+            (1.0 - t, t)
+        } else {
+            let angle = float::acos(cos);
+            let (sin, _) = float::sin_cos(angle);
+            (
+                float::sin_cos((1.0 - t) * angle).0 / sin,
+                float::sin_cos(t * angle).0 / sin,
+            )
+        };
+        Quat::new(
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z,
+            a * self.w + b * other.w,
+        )
+        .normalize()
+    }
+
+    /// Returns the quaternion scaled to unit length, or [`Quat::IDENTITY`] if its length is zero.
+    pub fn normalize(self) -> Quat {
+        let len = self.length();
+        if len == 0.0 {
+            return Quat::IDENTITY;
+        }
+        Quat::new(self.x / len, self.y / len, self.z / len, self.w / len)
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+
+    /// Rotates the vector.
+    fn mul(self, v: Vec3) -> Vec3 {
+        let u = Vec3::new(self.x, self.y, self.z);
+        let t = u.cross(v) * 2.0;
+        v + t * self.w + u.cross(t)
+    }
+}
+
+impl Mul for Quat {
+    type Output = Quat;
+
+    /// Composes two rotations: `a * b` applies `b` first, then `a`.
+    fn mul(self, rhs: Quat) -> Quat {
+        Quat::new(
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        )
+    }
+}
+
+/// Order in which the axis rotations of an [`Euler`] are applied, named like `THREE.Euler.order`.
+///
+/// The discriminants match the `RotationOrder` enum in `index.ts`.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RotationOrder {
+    #[default]
+    XYZ = 4001,
+    YXZ = 4002,
+    ZXY = 4003,
+    ZYX = 4004,
+    YZX = 4005,
+    XZY = 4006,
+}
+
+/// Rotation as three angles in radians, applied in `order`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Euler {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub order: RotationOrder,
+}
+
+impl Euler {
+    #[inline(always)]
+    pub const fn new(x: f32, y: f32, z: f32, order: RotationOrder) -> Self {
+        Euler { x, y, z, order }
+    }
+
+    /// Angles of a (normalized) quaternion in [`RotationOrder::XYZ`], matching
+    /// `THREE.Euler.setFromQuaternion`.
+    pub fn from_quat(q: Quat) -> Self {
+        let Quat { x, y, z, w } = q;
+        let m11 = 1.0 - 2.0 * (y * y + z * z);
+        let m12 = 2.0 * (x * y - w * z);
+        let m13 = 2.0 * (x * z + w * y);
+        let m22 = 1.0 - 2.0 * (x * x + z * z);
+        let m23 = 2.0 * (y * z - w * x);
+        let m32 = 2.0 * (y * z + w * x);
+        let m33 = 1.0 - 2.0 * (x * x + y * y);
+
+        let ey = float::asin(m13.clamp(-1.0, 1.0));
+        let (ex, ez) = if m13.abs() < 0.999_999_9 {
+            (float::atan2(-m23, m33), float::atan2(-m12, m11))
+        } else {
+            // gimbal lock, only the sum of x and z matters
+            (float::atan2(m32, m22), 0.0)
+        };
+        Euler::new(ex, ey, ez, RotationOrder::XYZ)
+    }
+
+    /// The same rotation as a quaternion, matching `THREE.Quaternion.setFromEuler`.
+    pub fn to_quat(self) -> Quat {
+        let qx = Quat::from_axis_angle(Vec3::X, self.x);
+        let qy = Quat::from_axis_angle(Vec3::Y, self.y);
+        let qz = Quat::from_axis_angle(Vec3::Z, self.z);
+        match self.order {
+            RotationOrder::XYZ => qx * qy * qz,
+            RotationOrder::YXZ => qy * qx * qz,
+            RotationOrder::ZXY => qz * qx * qy,
+            RotationOrder::ZYX => qz * qy * qx,
+            RotationOrder::YZX => qy * qz * qx,
+            RotationOrder::XZY => qx * qz * qy,
+        }
+    }
+}
+
+/// Position, Euler rotation (radians) and scale of an object, as pushed by `ctx::set_transform`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub position: Vec3,
+    pub rotation: Vec3,
+    pub scale: Vec3,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        position: Vec3::ZERO,
+        rotation: Vec3::ZERO,
+        scale: Vec3::ONE,
+    };
+
+    /// The rotation as a quaternion. The angles are applied in the three.js default order, XYZ.
+    pub fn rotation_quat(&self) -> Quat {
+        let r = self.rotation;
+        Euler::new(r.x, r.y, r.z, RotationOrder::XYZ).to_quat()
+    }
+
+    /// Matrix which scales, then rotates, then translates, like `THREE.Object3D.matrix`.
+    pub fn to_matrix(&self) -> Mat4 {
+        Mat4::from_translation(self.position)
+            * Mat4::from_rotation(self.rotation_quat())
+            * Mat4::from_scale(self.scale)
+    }
+}
+
+/// World transform of `child` when it is attached to `parent`, as with
+/// [`ctx::add_child`](crate::ctx::add_child).
+///
+/// Transforms can only express scaling along the object's own axes, so the result is exact
+/// when the parent is scaled uniformly or the child is not rotated. Otherwise the child would
+/// be sheared, which is lost.
+pub fn compose(parent: &Transform, child: &Transform) -> Transform {
+    let parent_rotation = parent.rotation_quat();
+    let scaled = Vec3::new(
+        child.position.x * parent.scale.x,
+        child.position.y * parent.scale.y,
+        child.position.z * parent.scale.z,
+    );
+    let rotation = Euler::from_quat(parent_rotation * child.rotation_quat());
+    Transform {
+        position: parent.position + parent_rotation * scaled,
+        rotation: Vec3::new(rotation.x, rotation.y, rotation.z),
+        scale: Vec3::new(
+            parent.scale.x * child.scale.x,
+            parent.scale.y * child.scale.y,
+            parent.scale.z * child.scale.z,
+        ),
+    }
+}
+
+/// Axis convention of the caller's coordinates, see [`crate::ctx::set_coordinate_system`].
+///
+/// Every system keeps X pointing right; they differ in which three.js axis is negated:
+///
+/// | system            | Y    | Z                    | negated three.js axis |
+/// |-------------------|------|----------------------|-----------------------|
+/// | `RightHandedYUp`  | up   | towards the viewer   | none                  |
+/// | `LeftHandedYUp`   | up   | away from the viewer | Z                     |
+/// | `LeftHandedYDown` | down | towards the viewer   | Y                     |
+///
+/// Negating one axis turns the handedness around, so rotation angles about the negated
+/// axis stay the same while the angles about the other two axes change sign.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoordinateSystem {
+    /// The three.js convention, where nothing is converted.
+    #[default]
+    RightHandedYUp,
+    LeftHandedYUp,
+    LeftHandedYDown,
+}
+
+impl CoordinateSystem {
+    /// Per-axis factors by which positions are multiplied.
+    fn signs(self) -> Vec3 {
+        match self {
+            CoordinateSystem::RightHandedYUp => Vec3::ONE,
+            CoordinateSystem::LeftHandedYUp => Vec3::new(1.0, 1.0, -1.0),
+            CoordinateSystem::LeftHandedYDown => Vec3::new(1.0, -1.0, 1.0),
+        }
+    }
+
+    /// Converts a position or direction between this system and three.js. The conversion is
+    /// its own inverse, so this works both ways.
+    pub fn convert_point(self, v: Vec3) -> Vec3 {
+        let s = self.signs();
+        Vec3::new(v.x * s.x, v.y * s.y, v.z * s.z)
+    }
+
+    /// Converts Euler angles between this system and three.js, both ways like
+    /// [`CoordinateSystem::convert_point`]. Works for any [`RotationOrder`].
+    pub fn convert_rotation(self, r: Vec3) -> Vec3 {
+        if self == CoordinateSystem::RightHandedYUp {
+            return r;
+        }
+        // an angle keeps its sign only about the negated axis
+        let s = self.signs();
+        Vec3::new(-r.x * s.x, -r.y * s.y, -r.z * s.z)
+    }
+}
+
+/// 4x4 matrix stored in column-major order, like `THREE.Matrix4.elements`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4(pub [f32; 16]);
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Mat4::identity()
+    }
+}
+
+impl Mat4 {
+    #[rustfmt::skip]
+    pub const fn identity() -> Self {
+        Mat4([
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub const fn from_translation(v: Vec3) -> Self {
+        Mat4([
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            v.x, v.y, v.z, 1.0,
+        ])
+    }
+
+    /// Rotation matrix of a (normalized) quaternion.
+    #[rustfmt::skip]
+    pub fn from_rotation(q: Quat) -> Self {
+        let Quat { x, y, z, w } = q;
+        let (x2, y2, z2) = (x + x, y + y, z + z);
+        let (xx, xy, xz) = (x * x2, x * y2, x * z2);
+        let (yy, yz, zz) = (y * y2, y * z2, z * z2);
+        let (wx, wy, wz) = (w * x2, w * y2, w * z2);
+        Mat4([
+            1.0 - (yy + zz), xy + wz, xz - wy, 0.0,
+            xy - wz, 1.0 - (xx + zz), yz + wx, 0.0,
+            xz + wy, yz - wx, 1.0 - (xx + yy), 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ])
+    }
+
+    #[rustfmt::skip]
+    pub const fn from_scale(v: Vec3) -> Self {
+        Mat4([
+            v.x, 0.0, 0.0, 0.0,
+            0.0, v.y, 0.0, 0.0,
+            0.0, 0.0, v.z, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ])
+    }
+
+    /// Element at `row`, `col`.
+    #[inline(always)]
+    pub fn get(&self, row: usize, col: usize) -> f32 {
+        self.0[col * 4 + row]
+    }
+
+    /// Transforms a point (w = 1).
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        let m = &self.0;
+        Vec3::new(
+            m[0] * p.x + m[4] * p.y + m[8] * p.z + m[12],
+            m[1] * p.x + m[5] * p.y + m[9] * p.z + m[13],
+            m[2] * p.x + m[6] * p.y + m[10] * p.z + m[14],
+        )
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    /// Composes two transforms: `a * b` applies `b` first, then `a`.
+    fn mul(self, rhs: Mat4) -> Mat4 {
+        let mut out = [0.0; 16];
+        for col in 0..4 {
+            for row in 0..4 {
+                out[col * 4 + row] = (0..4).map(|k| self.get(row, k) * rhs.get(k, col)).sum();
+            }
+        }
+        Mat4(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-6;
+
+    fn quat_approx_eq(a: Quat, b: Quat) -> bool {
+        (a.x - b.x).abs() < EPSILON
+            && (a.y - b.y).abs() < EPSILON
+            && (a.z - b.z).abs() < EPSILON
+            && (a.w - b.w).abs() < EPSILON
+    }
+
+    #[test]
+    fn zero_angle_is_identity() {
+        assert!(quat_approx_eq(
+            Quat::from_axis_angle(Vec3::Y, 0.0),
+            Quat::IDENTITY
+        ));
+    }
+
+    #[test]
+    fn quat_mul_composes_rotations() {
+        let quarter = Quat::from_axis_angle(Vec3::Z, core::f32::consts::FRAC_PI_2);
+        let half = Quat::from_axis_angle(Vec3::Z, core::f32::consts::PI);
+        assert!(quat_approx_eq(quarter * quarter, half));
+        assert!(quat_approx_eq(Quat::IDENTITY * half, half));
+    }
+
+    #[test]
+    fn quat_normalize() {
+        let q = Quat::new(0.0, 2.0, 0.0, 0.0).normalize();
+        assert!(quat_approx_eq(q, Quat::new(0.0, 1.0, 0.0, 0.0)));
+        assert_eq!(Quat::new(0.0, 0.0, 0.0, 0.0).normalize(), Quat::IDENTITY);
+    }
+
+    fn vec3_approx_eq(a: Vec3, b: Vec3) -> bool {
+        (a.x - b.x).abs() < EPSILON && (a.y - b.y).abs() < EPSILON && (a.z - b.z).abs() < EPSILON
+    }
+
+    #[test]
+    fn translation_moves_origin() {
+        let v = Vec3::new(1.0, -2.0, 3.0);
+        assert_eq!(Mat4::from_translation(v).transform_point(Vec3::ZERO), v);
+    }
+
+    #[test]
+    fn mat4_mul_applies_right_hand_side_first() {
+        let scale = Mat4::from_scale(Vec3::new(2.0, 2.0, 2.0));
+        let translation = Mat4::from_translation(Vec3::X);
+        let p = (translation * scale).transform_point(Vec3::ONE);
+        assert!(vec3_approx_eq(p, Vec3::new(3.0, 2.0, 2.0)));
+        assert_eq!(Mat4::identity() * scale, scale);
+    }
+
+    #[test]
+    fn rotation_matrix_matches_quaternion() {
+        let q = Quat::from_axis_angle(Vec3::Z, core::f32::consts::FRAC_PI_2);
+        let p = Mat4::from_rotation(q).transform_point(Vec3::X);
+        assert!(vec3_approx_eq(p, Vec3::Y));
+    }
+
+    #[test]
+    fn flatten_interleaves_xyz() {
+        let points = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0)];
+        assert_eq!(flatten(&points), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert!(flatten(&[]).is_empty());
+    }
+
+    #[test]
+    fn euler_to_quat_respects_order() {
+        let (a, b) = (0.3, -1.2);
+        let qx = Quat::from_axis_angle(Vec3::X, a);
+        let qy = Quat::from_axis_angle(Vec3::Y, b);
+        let xyz = Euler::new(a, b, 0.0, RotationOrder::XYZ).to_quat();
+        let yxz = Euler::new(a, b, 0.0, RotationOrder::YXZ).to_quat();
+        assert!(quat_approx_eq(xyz, qx * qy));
+        assert!(quat_approx_eq(yxz, qy * qx));
+        assert!(!quat_approx_eq(xyz, yxz));
+        assert_eq!(Euler::default().order, RotationOrder::XYZ);
+    }
+
+    #[test]
+    fn lerp_endpoints_and_midpoint() {
+        assert_eq!(lerp(2.0, 4.0, 0.0), 2.0);
+        assert_eq!(lerp(2.0, 4.0, 1.0), 4.0);
+        assert_eq!(lerp(2.0, 4.0, 0.5), 3.0);
+
+        let (a, b) = (Vec3::new(0.0, 2.0, -2.0), Vec3::new(2.0, 4.0, 2.0));
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Vec3::new(1.0, 3.0, 0.0));
+    }
+
+    #[test]
+    fn slerp_endpoints_and_midpoint() {
+        let a = Quat::IDENTITY;
+        let b = Quat::from_axis_angle(Vec3::Z, core::f32::consts::FRAC_PI_2);
+        assert!(quat_approx_eq(a.slerp(b, 0.0), a));
+        assert!(quat_approx_eq(a.slerp(b, 1.0), b));
+        assert!(quat_approx_eq(
+            a.slerp(b, 0.5),
+            Quat::from_axis_angle(Vec3::Z, core::f32::consts::FRAC_PI_4)
+        ));
+    }
+
+    #[test]
+    fn slerp_stays_normalized() {
+        let a = Quat::from_axis_angle(Vec3::X, 0.3);
+        let b = Quat::from_axis_angle(Vec3::Y, -2.5);
+        for i in 0..=10 {
+            let q = a.slerp(b, i as f32 / 10.0);
+            assert!((q.length() - 1.0).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn vec3_products() {
+        assert_eq!(Vec3::X.cross(Vec3::Y), Vec3::Z);
+        assert_eq!(Vec3::Y.cross(Vec3::Z), Vec3::X);
+        assert_eq!(Vec3::Y.cross(Vec3::X), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(
+            Vec3::new(1.0, 2.0, 3.0).dot(Vec3::new(4.0, -5.0, 6.0)),
+            12.0
+        );
+        assert_eq!(Vec3::X.dot(Vec3::Y), 0.0);
+    }
+
+    #[test]
+    fn vec3_length_and_normalize() {
+        let v = Vec3::new(3.0, 0.0, 4.0);
+        assert_eq!(v.length_squared(), 25.0);
+        assert_eq!(v.length(), 5.0);
+        assert!(vec3_approx_eq(v.normalize(), Vec3::new(0.6, 0.0, 0.8)));
+        assert_eq!(Vec3::ZERO.normalize(), Vec3::ZERO);
+        assert_eq!(
+            Vec3::new(1.0, 1.0, 1.0).distance(Vec3::new(1.0, 4.0, 5.0)),
+            5.0
+        );
+    }
+
+    fn mat4_approx_eq(a: Mat4, b: Mat4) -> bool {
+        a.0.iter().zip(b.0).all(|(x, y)| (x - y).abs() < 1e-5)
+    }
+
+    #[test]
+    fn euler_round_trips_through_quat() {
+        let e = Euler::new(0.4, -0.9, 2.1, RotationOrder::XYZ);
+        let back = Euler::from_quat(e.to_quat());
+        assert!(vec3_approx_eq(
+            Vec3::new(back.x, back.y, back.z),
+            Vec3::new(e.x, e.y, e.z)
+        ));
+    }
+
+    #[test]
+    fn compose_translated_parent_with_rotated_child() {
+        let parent = Transform {
+            position: Vec3::new(1.0, 2.0, 3.0),
+            ..Transform::IDENTITY
+        };
+        let child = Transform {
+            position: Vec3::X,
+            rotation: Vec3::new(0.0, 0.0, core::f32::consts::FRAC_PI_2),
+            ..Transform::IDENTITY
+        };
+        let world = compose(&parent, &child);
+        assert!(vec3_approx_eq(world.position, Vec3::new(2.0, 2.0, 3.0)));
+        assert!(vec3_approx_eq(world.rotation, child.rotation));
+        assert_eq!(world.scale, Vec3::ONE);
+    }
+
+    #[test]
+    fn compose_matches_matrix_product() {
+        let parent = Transform {
+            position: Vec3::new(-1.0, 0.5, 2.0),
+            rotation: Vec3::new(0.3, 1.1, -0.4),
+            scale: Vec3::new(2.0, 2.0, 2.0),
+        };
+        let child = Transform {
+            position: Vec3::new(0.5, -1.0, 1.5),
+            rotation: Vec3::new(-0.7, 0.2, 0.9),
+            scale: Vec3::new(1.0, 3.0, 0.5),
+        };
+        assert!(mat4_approx_eq(
+            compose(&parent, &child).to_matrix(),
+            parent.to_matrix() * child.to_matrix()
+        ));
+    }
+
+    #[test]
+    fn coordinate_system_flips_one_axis() {
+        let p = Vec3::new(1.0, 2.0, 3.0);
+        let r = Vec3::new(0.1, 0.2, 0.3);
+        assert_eq!(CoordinateSystem::RightHandedYUp.convert_point(p), p);
+        assert_eq!(CoordinateSystem::RightHandedYUp.convert_rotation(r), r);
+        assert_eq!(
+            CoordinateSystem::LeftHandedYDown.convert_point(p),
+            Vec3::new(1.0, -2.0, 3.0)
+        );
+        assert_eq!(
+            CoordinateSystem::LeftHandedYDown.convert_rotation(r),
+            Vec3::new(-0.1, 0.2, -0.3)
+        );
+        assert_eq!(
+            CoordinateSystem::LeftHandedYUp.convert_point(p),
+            Vec3::new(1.0, 2.0, -3.0)
+        );
+        assert_eq!(
+            CoordinateSystem::LeftHandedYUp.convert_rotation(r),
+            Vec3::new(-0.1, -0.2, 0.3)
+        );
+    }
+
+    #[test]
+    fn converted_rotation_matches_mirrored_points() {
+        // rotating a converted point must equal converting the rotated point
+        let system = CoordinateSystem::LeftHandedYDown;
+        let r = Vec3::new(0.4, -0.7, 1.2);
+        let p = Vec3::new(0.5, 1.5, -2.0);
+        let rotate = |r: Vec3, p: Vec3| Euler::new(r.x, r.y, r.z, RotationOrder::XYZ).to_quat() * p;
+        assert!(vec3_approx_eq(
+            rotate(system.convert_rotation(r), system.convert_point(p)),
+            system.convert_point(rotate(r, p))
+        ));
+    }
+}
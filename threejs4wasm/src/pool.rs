@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+
+use crate::{GeometryClass, MaterialClass, ObjectId, ThreeError, ctx};
+
+/// Fixed set of pre-created objects which are shown when acquired and hidden when released,
+/// instead of being created and disposed every time they are needed.
+///
+/// All objects are added to the scene up front. A released object keeps its last transform,
+/// so set it again after [`ObjectPool::acquire`].
+#[derive(Debug)]
+pub struct ObjectPool {
+    free: Vec<ObjectId>,
+    in_use: Vec<ObjectId>,
+}
+
+impl ObjectPool {
+    /// Pool of `size` meshes with the given geometry and material.
+    pub fn new(
+        geometry: GeometryClass,
+        material: MaterialClass,
+        size: usize,
+    ) -> Result<Self, ThreeError> {
+        Self::from_fn(size, || ctx::create_object(geometry, material))
+    }
+
+    /// Pool of `size` objects made by `create`, e.g. sprites sharing one texture. If any
+    /// object can't be set up, the ones created so far are disposed again and the error
+    /// returned.
+    pub fn from_fn(
+        size: usize,
+        mut create: impl FnMut() -> Result<ObjectId, ThreeError>,
+    ) -> Result<Self, ThreeError> {
+        let mut free = Vec::with_capacity(size);
+        let result = (0..size).try_for_each(|_| {
+            let id = create()?;
+            free.push(id);
+            ctx::add_object_to_scene(id)?;
+            ctx::set_visible(id, false)
+        });
+        if let Err(err) = result {
+            for id in free {
+                let _ = ctx::dispose_object(id);
+            }
+            return Err(err);
+        }
+
+        Ok(ObjectPool {
+            free,
+            in_use: Vec::with_capacity(size),
+        })
+    }
+
+    /// Shows a free object and hands it out, or returns `None` if all objects are in use.
+    pub fn acquire(&mut self) -> Option<ObjectId> {
+        let &id = self.free.last()?;
+        ctx::set_visible(id, true).ok()?;
+        self.free.pop();
+        self.in_use.push(id);
+        Some(id)
+    }
+
+    /// Hides the object and makes it available again. Ids which were not acquired from
+    /// this pool are ignored.
+    pub fn release(&mut self, id: ObjectId) {
+        let Some(index) = self.in_use.iter().position(|&used| used == id) else {
+            return;
+        };
+        self.in_use.swap_remove(index);
+        let _ = ctx::set_visible(id, false);
+        self.free.push(id);
+    }
+
+    /// Number of objects which can still be acquired.
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Total number of objects in the pool.
+    pub fn capacity(&self) -> usize {
+        self.free.len() + self.in_use.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{self, Call};
+
+    #[test]
+    fn acquire_returns_none_until_released() {
+        let mut pool = ObjectPool::new(
+            GeometryClass::BoxGeometry,
+            MaterialClass::MeshBasicMaterial,
+            2,
+        )
+        .unwrap();
+        assert_eq!(pool.capacity(), 2);
+
+        let first = pool.acquire().unwrap();
+        assert!(pool.acquire().is_some());
+        assert_eq!(pool.acquire(), None);
+
+        pool.release(first);
+        assert_eq!(pool.available(), 1);
+        assert!(pool.acquire().is_some());
+        assert_eq!(pool.acquire(), None);
+    }
+
+    #[test]
+    fn failed_setup_disposes_created_objects() {
+        test::set_next_id(20);
+        test::take_calls();
+        let mut created = 0;
+        let result = ObjectPool::from_fn(3, || {
+            created += 1;
+            if created == 3 {
+                return Err(ThreeError::JsException);
+            }
+            ctx::create_object(GeometryClass::BoxGeometry, MaterialClass::MeshBasicMaterial)
+        });
+        assert_eq!(result.unwrap_err(), ThreeError::JsException);
+
+        let disposed: Vec<i32> = test::take_calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                Call::DisposeObject { object_id } => Some(object_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(disposed, [20, 21]);
+    }
+}
@@ -0,0 +1,89 @@
+use alloc::collections::BTreeMap;
+
+use crate::ObjectId;
+
+/// Associates game data with objects, e.g. to find the entity behind the id returned by
+/// [`crate::ctx::raycast_from_camera`].
+///
+/// Entries are not removed when the object is disposed, call [`ObjectRegistry::remove`]
+/// together with [`crate::ctx::dispose_object`].
+#[derive(Debug, Clone)]
+pub struct ObjectRegistry<T> {
+    entries: BTreeMap<ObjectId, T>,
+}
+
+impl<T> Default for ObjectRegistry<T> {
+    fn default() -> Self {
+        ObjectRegistry::new()
+    }
+}
+
+impl<T> ObjectRegistry<T> {
+    pub const fn new() -> Self {
+        ObjectRegistry {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Attaches `data` to the object, returning the data it had before, if any.
+    pub fn insert(&mut self, id: ObjectId, data: T) -> Option<T> {
+        self.entries.insert(id, data)
+    }
+
+    pub fn get(&self, id: ObjectId) -> Option<&T> {
+        self.entries.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: ObjectId) -> Option<&mut T> {
+        self.entries.get_mut(&id)
+    }
+
+    pub fn remove(&mut self, id: ObjectId) -> Option<T> {
+        self.entries.remove(&id)
+    }
+
+    pub fn contains(&self, id: ObjectId) -> bool {
+        self.entries.contains_key(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the entries in the order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = (ObjectId, &T)> {
+        self.entries.iter().map(|(&id, data)| (id, data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Entity {
+        Player { health: u32 },
+        Coin,
+    }
+
+    #[test]
+    fn round_trips_entities() {
+        let (player, coin) = (ObjectId::new(3), ObjectId::new(8));
+        let mut registry = ObjectRegistry::new();
+        assert_eq!(registry.insert(player, Entity::Player { health: 10 }), None);
+        assert_eq!(registry.insert(coin, Entity::Coin), None);
+
+        if let Some(Entity::Player { health }) = registry.get_mut(player) {
+            *health -= 1;
+        }
+        assert_eq!(registry.get(player), Some(&Entity::Player { health: 9 }));
+
+        assert_eq!(registry.remove(coin), Some(Entity::Coin));
+        assert_eq!(registry.get(coin), None);
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -0,0 +1,78 @@
+//! A small deterministic random number generator (PCG32), for game logic which has to be
+//! reproducible and shouldn't pull in `getrandom` on `wasm32-unknown-unknown`:
+//!
+//! ```ignore
+//! let mut rng = Rng::seed(42);
+//! for _ in 0..100 {
+//!     let tree = ctx::create_object(GeometryClass::ConeGeometry, MaterialClass::MeshLambertMaterial)?;
+//!     ctx::set_position(tree, rng.range(-50.0, 50.0), 0.0, rng.range(-50.0, 50.0))?;
+//! }
+//! ```
+
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+const INCREMENT: u64 = 1_442_695_040_888_963_407;
+
+/// PCG32 (XSH RR) generator. The same seed always yields the same sequence, on every target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn seed(seed: u64) -> Self {
+        let mut rng = Self { state: 0 };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(MULTIPLIER).wrapping_add(INCREMENT);
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    /// Uniformly distributed in `0.0..1.0`.
+    pub fn next_f32(&mut self) -> f32 {
+        // the top 24 bits fit the f32 mantissa exactly
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Uniformly distributed in `min..max`.
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::seed(42);
+        let mut b = Rng::seed(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+
+        assert_ne!(Rng::seed(42).next_u32(), Rng::seed(43).next_u32());
+    }
+
+    #[test]
+    fn floats_stay_in_range() {
+        let mut rng = Rng::seed(7);
+        for _ in 0..1000 {
+            let x = rng.next_f32();
+            assert!((0.0..1.0).contains(&x));
+            let y = rng.range(-2.0, 3.0);
+            assert!((-2.0..3.0).contains(&y));
+        }
+    }
+}
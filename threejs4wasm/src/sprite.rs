@@ -0,0 +1,100 @@
+/// Layout of an animated sprite texture: a grid of `columns` x `rows` frames, of which
+/// the first `frame_count` are used. Frames are numbered row by row, starting at frame
+/// `(0, 0)` as addressed by [`crate::ctx::set_sprite_animation_offset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpriteSheet {
+    pub columns: u32,
+    pub rows: u32,
+    pub frame_count: u32,
+}
+
+impl SpriteSheet {
+    /// Sheet where every cell of the grid holds a frame.
+    pub const fn new(columns: u32, rows: u32) -> Self {
+        SpriteSheet {
+            columns,
+            rows,
+            frame_count: columns * rows,
+        }
+    }
+
+    /// Maps a linear frame index to `(frame_x, frame_y)` grid coordinates.
+    /// Indices past `frame_count` wrap around, so a running counter can be passed directly.
+    pub fn frame_coords(&self, frame: u32) -> (i32, i32) {
+        if self.columns == 0 || self.frame_count == 0 {
+            return (0, 0);
+        }
+
+        let frame = frame % self.frame_count;
+        ((frame % self.columns) as i32, (frame / self.columns) as i32)
+    }
+}
+
+/// Part of a texture in UV coordinates, as used by `THREE.Texture.offset` and `repeat`:
+/// `(x, y)` is the bottom-left corner and everything goes from 0.0 to 1.0.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Handle to a texture atlas with named frames, loaded with [`crate::ctx::load_atlas`].
+///
+/// Where a [`SpriteSheet`] is a regular grid, an atlas packs frames of any size and addresses
+/// them by name, as exported by tools like TexturePacker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Atlas(i32);
+
+impl Atlas {
+    #[cfg(feature = "ffi")]
+    pub(crate) fn new(raw: i32) -> Self {
+        Atlas(raw)
+    }
+
+    /// Returns the raw id as understood by the JS side.
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+
+    /// Where the frame called `name` lies in the atlas texture, or `None` if there is no such
+    /// frame (or the atlas is still loading).
+    #[cfg(feature = "ffi")]
+    pub fn frame_rect(&self, name: &str) -> Option<Rect> {
+        crate::ctx::atlas_frame_rect(*self, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_coords_go_row_by_row() {
+        let sheet = SpriteSheet::new(3, 2);
+        assert_eq!(sheet.frame_coords(0), (0, 0));
+        assert_eq!(sheet.frame_coords(2), (2, 0));
+        assert_eq!(sheet.frame_coords(3), (0, 1));
+        assert_eq!(sheet.frame_coords(5), (2, 1));
+    }
+
+    #[test]
+    fn frame_coords_wrap_around() {
+        let sheet = SpriteSheet::new(2, 2);
+        assert_eq!(sheet.frame_coords(4), (0, 0));
+        assert_eq!(sheet.frame_coords(7), (1, 1));
+    }
+
+    #[test]
+    fn frame_coords_partially_filled_last_row() {
+        let sheet = SpriteSheet {
+            columns: 3,
+            rows: 3,
+            frame_count: 7,
+        };
+        assert_eq!(sheet.frame_coords(6), (0, 2));
+        assert_eq!(sheet.frame_coords(7), (0, 0));
+        assert_eq!(sheet.frame_coords(8), (1, 0));
+    }
+}
@@ -0,0 +1,164 @@
+//! Fixed-timestep updates decoupled from the render rate, for deterministic physics:
+//!
+//! ```ignore
+//! let mut physics = FixedLoop::new(1.0 / 60.0);
+//! ctx::run_loop(move |delta| {
+//!     let alpha = physics.advance(delta, || world.step(1.0 / 60.0));
+//!     world.draw_interpolated(alpha);
+//!     true
+//! })?;
+//! ```
+
+use crate::{Euler, Transform, Vec3};
+#[cfg(feature = "ffi")]
+use crate::{ObjectId, ThreeError};
+
+/// Longest frame [`FixedLoop::advance`] catches up on. Longer frames (e.g. after the tab was
+/// in the background) are cut to this, so a slow step can't snowball into ever more steps.
+pub const MAX_FRAME_TIME: f32 = 0.25;
+
+/// Accumulates frame times and consumes them in steps of `step` seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedLoop {
+    pub step: f32,
+    accumulator: f32,
+}
+
+impl FixedLoop {
+    pub const fn new(step: f32) -> Self {
+        FixedLoop {
+            step,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Adds `delta` seconds (usually [`ctx::delta_time`](crate::ctx::delta_time)) and runs
+    /// `step_fn` once for every whole step accumulated so far.
+    ///
+    /// Returns the leftover time as a fraction of a step in `0..1`, to interpolate between
+    /// the last two steps when drawing.
+    pub fn advance(&mut self, delta: f32, mut step_fn: impl FnMut()) -> f32 {
+        if self.step <= 0.0 {
+            return 0.0;
+        }
+        self.accumulator += delta.clamp(0.0, MAX_FRAME_TIME);
+        while self.accumulator >= self.step {
+            step_fn();
+            self.accumulator -= self.step;
+        }
+        self.alpha()
+    }
+
+    /// Leftover time of the last [`FixedLoop::advance`], as a fraction of a step.
+    pub fn alpha(&self) -> f32 {
+        if self.step <= 0.0 {
+            return 0.0;
+        }
+        self.accumulator / self.step
+    }
+}
+
+/// Transforms of the last two fixed steps of an object, drawn in between with the alpha
+/// returned by [`FixedLoop::advance`] so that movement looks smooth at any frame rate:
+///
+/// ```ignore
+/// let alpha = physics.advance(delta, || {
+///     world.step(STEP);
+///     player.push(world.player_transform());
+/// });
+/// player.render(player_id, alpha)?;
+/// ```
+///
+/// What is drawn lags up to one step behind the simulation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothedTransform {
+    pub previous: Transform,
+    pub current: Transform,
+}
+
+impl SmoothedTransform {
+    /// Starts at rest at `transform`.
+    pub const fn new(transform: Transform) -> Self {
+        SmoothedTransform {
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Records the transform of a new step, the old current one becomes the previous one.
+    pub fn push(&mut self, transform: Transform) {
+        self.previous = self.current;
+        self.current = transform;
+    }
+
+    /// Jumps to `transform` without interpolating, e.g. after respawning.
+    pub fn teleport(&mut self, transform: Transform) {
+        *self = Self::new(transform);
+    }
+
+    /// Transform `alpha` of the way from the previous to the current one. Position and scale
+    /// are interpolated linearly, the rotation along the shortest arc.
+    pub fn interpolate(&self, alpha: f32) -> Transform {
+        let (a, b) = (&self.previous, &self.current);
+        let rotation = Euler::from_quat(a.rotation_quat().slerp(b.rotation_quat(), alpha));
+        Transform {
+            position: a.position.lerp(b.position, alpha),
+            rotation: Vec3::new(rotation.x, rotation.y, rotation.z),
+            scale: a.scale.lerp(b.scale, alpha),
+        }
+    }
+
+    /// Pushes the interpolated transform to the object with
+    /// [`ctx::set_transform`](crate::ctx::set_transform).
+    #[cfg(feature = "ffi")]
+    pub fn render(&self, object_id: ObjectId, alpha: f32) -> Result<(), ThreeError> {
+        crate::ctx::set_transform(object_id, &self.interpolate(alpha))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consumes_whole_steps_and_keeps_remainder() {
+        let mut fixed = FixedLoop::new(0.016);
+        let mut steps = 0;
+        let alpha = fixed.advance(0.05, || steps += 1);
+        assert_eq!(steps, 3);
+        assert!((alpha - 0.125).abs() < 1e-4);
+
+        // the remainder carries over into the next frame
+        let alpha = fixed.advance(0.015, || steps += 1);
+        assert_eq!(steps, 4);
+        assert!((alpha - 0.0625).abs() < 1e-4);
+    }
+
+    #[test]
+    fn long_frames_are_capped() {
+        let mut fixed = FixedLoop::new(0.1);
+        let mut steps = 0;
+        fixed.advance(10.0, || steps += 1);
+        assert_eq!(steps, 2);
+    }
+
+    #[test]
+    fn smoothed_transform_endpoints() {
+        let mut smoothed = SmoothedTransform::new(Transform::IDENTITY);
+        let next = Transform {
+            position: Vec3::new(2.0, 0.0, 0.0),
+            rotation: Vec3::new(0.0, 1.0, 0.0),
+            scale: Vec3::new(3.0, 3.0, 3.0),
+        };
+        smoothed.push(next);
+
+        assert_eq!(smoothed.interpolate(0.0), Transform::IDENTITY);
+        let end = smoothed.interpolate(1.0);
+        assert_eq!((end.position, end.scale), (next.position, next.scale));
+        assert!((end.rotation.y - 1.0).abs() < 1e-5);
+
+        let mid = smoothed.interpolate(0.5);
+        assert_eq!(mid.position, Vec3::new(1.0, 0.0, 0.0));
+        assert!((mid.rotation.y - 0.5).abs() < 1e-5);
+    }
+}
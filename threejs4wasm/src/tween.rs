@@ -0,0 +1,152 @@
+//! Interpolation of values over time, e.g. fading a material with
+//! [`ctx::set_material_opacity`](crate::ctx::set_material_opacity):
+//!
+//! ```ignore
+//! let fade = Tween::new(0.0, 1.0, 0.5, Easing::EaseOutCubic);
+//! let mut elapsed = 0.0;
+//! ctx::run_loop(move |delta| {
+//!     elapsed += delta;
+//!     ctx::set_material_opacity(obj, fade.sample(elapsed), Some(true)).is_ok()
+//! })?;
+//! ```
+
+use crate::{Color, Quat, Vec3};
+
+/// Shape of the progression from the start to the end value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Starts slow and accelerates.
+    EaseInQuad,
+    /// Starts fast and decelerates.
+    EaseOutCubic,
+}
+
+impl Easing {
+    /// Maps linear progress `t` in `0..=1` to eased progress in `0..=1`.
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutCubic => {
+                let inv = 1.0 - t;
+                1.0 - inv * inv * inv
+            }
+        }
+    }
+}
+
+/// Values a [`Tween`] can interpolate.
+pub trait Lerp: Copy {
+    /// Value at `t` between `self` (0) and `to` (1).
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: f32, t: f32) -> f32 {
+        crate::math::lerp(self, to, t)
+    }
+}
+
+impl Lerp for Vec3 {
+    fn lerp(self, to: Vec3, t: f32) -> Vec3 {
+        Vec3::lerp(self, to, t)
+    }
+}
+
+impl Lerp for Quat {
+    fn lerp(self, to: Quat, t: f32) -> Quat {
+        Quat::slerp(self, to, t)
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(self, to: Color, t: f32) -> Color {
+        Color::lerp(self, to, t)
+    }
+}
+
+/// Transition from `start` to `end` over `duration` seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween<T> {
+    pub start: T,
+    pub end: T,
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub const fn new(start: T, end: T, duration: f32, easing: Easing) -> Self {
+        Tween {
+            start,
+            end,
+            duration,
+            easing,
+        }
+    }
+
+    /// Value after `elapsed` seconds. Times before 0 give `start`, times past `duration`
+    /// give `end`.
+    pub fn sample(&self, elapsed: f32) -> T {
+        self.start
+            .lerp(self.end, self.easing.apply(self.progress(elapsed)))
+    }
+
+    /// Linear progress in `0..=1` after `elapsed` seconds.
+    pub fn progress(&self, elapsed: f32) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        (elapsed / self.duration).clamp(0.0, 1.0)
+    }
+
+    pub fn is_finished(&self, elapsed: f32) -> bool {
+        elapsed >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_clamps_outside_duration() {
+        let tween = Tween::new(1.0, 3.0, 2.0, Easing::Linear);
+        assert_eq!(tween.sample(-1.0), 1.0);
+        assert_eq!(tween.sample(1.0), 2.0);
+        assert_eq!(tween.sample(2.0), 3.0);
+        assert_eq!(tween.sample(10.0), 3.0);
+        assert!(tween.is_finished(10.0));
+    }
+
+    #[test]
+    fn easings_keep_endpoints() {
+        for easing in [Easing::Linear, Easing::EaseInQuad, Easing::EaseOutCubic] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+        assert!(Easing::EaseInQuad.apply(0.5) < 0.5);
+        assert!(Easing::EaseOutCubic.apply(0.5) > 0.5);
+    }
+
+    #[test]
+    fn vec3_and_color_clamp_past_end() {
+        let tween = Tween::new(
+            Vec3::ZERO,
+            Vec3::new(2.0, 4.0, -2.0),
+            1.0,
+            Easing::EaseOutCubic,
+        );
+        assert_eq!(tween.sample(5.0), Vec3::new(2.0, 4.0, -2.0));
+
+        let tween = Tween::new(Color::BLACK, Color::WHITE, 1.0, Easing::Linear);
+        assert_eq!(tween.sample(0.5), Color::rgb(128, 128, 128));
+        assert_eq!(tween.sample(5.0), Color::WHITE);
+    }
+
+    #[test]
+    fn zero_duration_jumps_to_end() {
+        assert_eq!(Tween::new(0.0, 1.0, 0.0, Easing::Linear).sample(0.0), 1.0);
+    }
+}